@@ -0,0 +1,76 @@
+#![no_main]
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::{Bound, RangeBounds};
+use std::panic::{self, AssertUnwindSafe};
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use ordered_collections::{ChunkedOrderedSet, OrderedMap, OrderedSet};
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+    items: Vec<u16>,
+    start: Bound<u16>,
+    end: Bound<u16>,
+}
+
+// Ranges for which `BTreeMap::range` (and so our `range`) must panic.
+fn is_invalid(start: Bound<&u16>, end: Bound<&u16>) -> bool {
+    use Bound::*;
+    match (start, end) {
+        (Excluded(s), Excluded(e)) => s >= e,
+        (Included(s), Included(e)) | (Included(s), Excluded(e)) | (Excluded(s), Included(e)) => {
+            s > e
+        }
+        _ => false,
+    }
+}
+
+// Return `true` if `f` panics. The panic hook installed by `libfuzzer_sys` aborts the
+// process so it is replaced by a silent one while `f` runs.
+fn panics<F: FnOnce()>(f: F) -> bool {
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    panic::set_hook(hook);
+    result.is_err()
+}
+
+fuzz_target!(|input: Input| {
+    let bounds = (input.start, input.end);
+    let model: BTreeSet<u16> = input.items.iter().cloned().collect();
+    let set: OrderedSet<u16> = input.items.iter().cloned().collect();
+    let chunked: ChunkedOrderedSet<u16> = input.items.iter().cloned().collect();
+    let map_model: BTreeMap<u16, usize> = input.items.iter().cloned().zip(0..).collect();
+    let map: OrderedMap<u16, usize> = input.items.iter().cloned().zip(0..).collect();
+
+    if is_invalid(bounds.start_bound(), bounds.end_bound()) {
+        assert!(panics(|| {
+            let _ = model.range(bounds);
+        }));
+        assert!(panics(|| {
+            let _ = map_model.range(bounds);
+        }));
+        assert!(panics(|| {
+            let _ = set.range(bounds);
+        }));
+        assert!(panics(|| {
+            let _ = chunked.range(bounds);
+        }));
+        assert!(panics(|| {
+            let _ = map.range(bounds);
+        }));
+        return;
+    }
+
+    let expected: Vec<u16> = model.range(bounds).cloned().collect();
+    let found: Vec<u16> = set.range(bounds).cloned().collect();
+    assert_eq!(found, expected);
+    let found: Vec<u16> = chunked.range(bounds).cloned().collect();
+    assert_eq!(found, expected);
+
+    let expected: Vec<(u16, usize)> = map_model.range(bounds).map(|(k, v)| (*k, *v)).collect();
+    let found: Vec<(u16, usize)> = map.range(bounds).map(|(k, v)| (*k, *v)).collect();
+    assert_eq!(found, expected);
+});
@@ -0,0 +1,72 @@
+#![no_main]
+
+use std::collections::BTreeSet;
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use ordered_collections::ordered_set::ord_set_iterators::IterSetOperations;
+use ordered_collections::OrderedSet;
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+    a: Vec<u16>,
+    b: Vec<u16>,
+    c: Vec<u16>,
+}
+
+fuzz_target!(|input: Input| {
+    let a: OrderedSet<u16> = input.a.iter().cloned().collect();
+    let b: OrderedSet<u16> = input.b.iter().cloned().collect();
+    let c: OrderedSet<u16> = input.c.iter().cloned().collect();
+    let model_a: BTreeSet<u16> = input.a.iter().cloned().collect();
+    let model_b: BTreeSet<u16> = input.b.iter().cloned().collect();
+    let model_c: BTreeSet<u16> = input.c.iter().cloned().collect();
+
+    assert!(a.iter().union(b.iter()).eq(model_a.union(&model_b)));
+    assert!(a
+        .iter()
+        .intersection(b.iter())
+        .eq(model_a.intersection(&model_b)));
+    assert!(a
+        .iter()
+        .difference(b.iter())
+        .eq(model_a.difference(&model_b)));
+    assert!(a
+        .iter()
+        .symmetric_difference(b.iter())
+        .eq(model_a.symmetric_difference(&model_b)));
+
+    // Nested operations exercise the skip-ahead paths of the set operation iterators.
+    let expected: BTreeSet<u16> = &(&model_a | &model_b) & &model_c;
+    assert!(a
+        .iter()
+        .union(b.iter())
+        .intersection(c.iter())
+        .eq(expected.iter()));
+    let expected: BTreeSet<u16> = &(&model_a ^ &model_b) - &model_c;
+    assert!(a
+        .iter()
+        .symmetric_difference(b.iter())
+        .difference(c.iter())
+        .eq(expected.iter()));
+
+    assert_eq!(&a | &b, model_a.union(&model_b).cloned().collect());
+    assert_eq!(&a & &b, model_a.intersection(&model_b).cloned().collect());
+    assert_eq!(&a - &b, model_a.difference(&model_b).cloned().collect());
+    assert_eq!(
+        &a ^ &b,
+        model_a.symmetric_difference(&model_b).cloned().collect()
+    );
+
+    assert_eq!(a.is_disjoint(&b), model_a.is_disjoint(&model_b));
+    assert_eq!(a.is_subset(&b), model_a.is_subset(&model_b));
+    assert_eq!(a.is_superset(&b), model_a.is_superset(&model_b));
+    assert_eq!(
+        a.is_proper_subset(&b),
+        model_a.is_subset(&model_b) && model_a.len() < model_b.len()
+    );
+    assert_eq!(
+        a.is_proper_superset(&b),
+        model_a.is_superset(&model_b) && model_a.len() > model_b.len()
+    );
+});
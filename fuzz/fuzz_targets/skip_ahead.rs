@@ -0,0 +1,134 @@
+#![no_main]
+
+use std::borrow::Borrow;
+use std::collections::BTreeSet;
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use ordered_collections::ordered_set::ord_set_iterators::{IterSetOperations, SkipAheadIterator};
+use ordered_collections::{BTreeSetRange, OrderedSet};
+
+#[derive(Arbitrary, Debug)]
+enum Kind {
+    Plain,
+    Union,
+    Intersection,
+    Difference,
+    SymmetricDifference,
+    RangeIntersection(u16, u16),
+    RangeUnion(u16, u16, u16, u16),
+    BTreeRange(u16, u16),
+}
+
+#[derive(Arbitrary, Debug)]
+enum Op {
+    Next,
+    Peek,
+    AdvancePast(u16),
+    AdvanceUntil(u16),
+}
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+    a: Vec<u16>,
+    b: Vec<u16>,
+    kind: Kind,
+    ops: Vec<Op>,
+}
+
+// Apply `ops` to `iter` and to a cursor into `expected` (the items the iterator
+// should yield) and check that they agree at every step.
+fn check<I>(mut iter: I, expected: &[u16], ops: &[Op])
+where
+    I: SkipAheadIterator<u16>,
+    I::Item: Borrow<u16>,
+{
+    let mut index = 0;
+    for op in ops {
+        match op {
+            Op::Next => {
+                assert_eq!(
+                    iter.next().map(|item| *item.borrow()),
+                    expected.get(index).cloned()
+                );
+                index = (index + 1).min(expected.len());
+            }
+            Op::Peek => assert_eq!(iter.peek(), expected.get(index)),
+            Op::AdvancePast(item) => {
+                iter.advance_past(item);
+                while index < expected.len() && expected[index] <= *item {
+                    index += 1;
+                }
+            }
+            Op::AdvanceUntil(item) => {
+                iter.advance_until(item);
+                while index < expected.len() && expected[index] < *item {
+                    index += 1;
+                }
+            }
+        }
+    }
+    assert!(iter
+        .map(|item| *item.borrow())
+        .eq(expected[index..].iter().cloned()));
+}
+
+fuzz_target!(|input: Input| {
+    let a: OrderedSet<u16> = input.a.iter().cloned().collect();
+    let b: OrderedSet<u16> = input.b.iter().cloned().collect();
+    let model_a: BTreeSet<u16> = input.a.iter().cloned().collect();
+    let model_b: BTreeSet<u16> = input.b.iter().cloned().collect();
+    match input.kind {
+        Kind::Plain => {
+            let expected: Vec<u16> = model_a.iter().cloned().collect();
+            check(a.iter(), &expected, &input.ops);
+        }
+        Kind::Union => {
+            let expected: Vec<u16> = model_a.union(&model_b).cloned().collect();
+            check(a.iter().union(b.iter()), &expected, &input.ops);
+        }
+        Kind::Intersection => {
+            let expected: Vec<u16> = model_a.intersection(&model_b).cloned().collect();
+            check(a.iter().intersection(b.iter()), &expected, &input.ops);
+        }
+        Kind::Difference => {
+            let expected: Vec<u16> = model_a.difference(&model_b).cloned().collect();
+            check(a.iter().difference(b.iter()), &expected, &input.ops);
+        }
+        Kind::SymmetricDifference => {
+            let expected: Vec<u16> = model_a.symmetric_difference(&model_b).cloned().collect();
+            check(
+                a.iter().symmetric_difference(b.iter()),
+                &expected,
+                &input.ops,
+            );
+        }
+        Kind::RangeIntersection(start, end) => {
+            let expected: Vec<u16> = model_a.range(start..end.max(start)).cloned().collect();
+            check((start..end).intersection(a.iter()), &expected, &input.ops);
+        }
+        Kind::RangeUnion(start1, end1, start2, end2) => {
+            let expected: BTreeSet<u16> = (start1..end1).chain(start2..end2).collect();
+            let expected: Vec<u16> = expected.into_iter().collect();
+            check((start1..end1).union(start2..end2), &expected, &input.ops);
+        }
+        Kind::BTreeRange(start, end) => {
+            let range = (start, end.max(start));
+            let expected: Vec<u16> = model_b.range(range.0..range.1).cloned().collect();
+            check(
+                BTreeSetRange::new(&model_b, range.0..range.1),
+                &expected,
+                &input.ops,
+            );
+            let expected: Vec<u16> = model_a
+                .intersection(&model_b.range(range.0..).cloned().collect())
+                .cloned()
+                .collect();
+            check(
+                a.iter() & BTreeSetRange::new(&model_b, range.0..),
+                &expected,
+                &input.ops,
+            );
+        }
+    }
+});
@@ -0,0 +1,812 @@
+//! Adapters that let iterators from outside the crate take part in its set algebra and
+//! map filtering.
+//!
+//! `Sorted` wraps any iterator whose output is already in ascending order (without
+//! duplicates) and advances by stepping through the items. `Checked` verifies that a
+//! `SkipAheadIterator`'s output really is in that order. `BTreeSetRange` and
+//! `BTreeMapRange` visit a range of a `BTreeSet` or `BTreeMap` and advance by starting
+//! a new range search at the target.
+
+#[cfg(feature = "alloc")]
+use alloc::collections::{btree_map, btree_set, BTreeMap, BTreeSet};
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::fmt;
+use core::marker::PhantomData;
+#[cfg(feature = "alloc")]
+use core::ops::{BitAnd, BitOr, BitXor, Bound, RangeBounds, Sub};
+#[cfg(feature = "alloc")]
+use core::ptr;
+
+#[cfg(feature = "alloc")]
+use crate::ordered_map::ord_map_iterators::ToMap;
+use crate::ordered_map::ord_map_iterators::{MapIterFilter, MapIterMerge, SkipAheadMapIterator};
+#[cfg(feature = "alloc")]
+use crate::ordered_set::ord_set_iterators::{
+    Difference, Intersection, SymmetricDifference, ToList, ToSet, Union,
+};
+use crate::ordered_set::ord_set_iterators::{IterSetOperations, Selection, SkipAheadIterator};
+
+// SORTED ITERATOR ADAPTER
+
+/// An adapter that makes an iterator whose output is in ascending order and contains no
+/// duplicates a `SkipAheadIterator` (or, if its items are key-value pairs of references,
+/// a `SkipAheadMapIterator`). Its items may be references, owned values or anything
+/// else that borrows as the type being compared.
+///
+/// The ordering of the wrapped iterator's output is not checked and the set operations
+/// will give incorrect results if it is out of order. The first item is read from the
+/// wrapped iterator when the adapter is created. As the items may borrow as more than
+/// one type the compared type may need to be named when the advance methods are called
+/// directly (e.g. `SkipAheadIterator::<u32>::advance_past(&mut sorted, &20)`).
+pub struct Sorted<I: Iterator> {
+    iter: I,
+    next: Option<I::Item>,
+}
+
+impl<I: Iterator> Sorted<I> {
+    /// Create a `Sorted` adapter for an iterator whose output is in ascending order.
+    pub fn new<J: IntoIterator<IntoIter = I>>(iter: J) -> Self {
+        let mut iter = iter.into_iter();
+        let next = iter.next();
+        Self { iter, next }
+    }
+
+    // Step through the items until `before` is false for the next one.
+    fn skip_before<F: Fn(&I::Item) -> bool>(&mut self, before: F) {
+        while let Some(item) = &self.next {
+            if !before(item) {
+                break;
+            }
+            self.next = self.iter.next();
+        }
+    }
+}
+
+impl<I: Iterator> Iterator for Sorted<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.next.take()?;
+        self.next = self.iter.next();
+        Some(next)
+    }
+}
+
+impl<T, I> SkipAheadIterator<T> for Sorted<I>
+where
+    T: Ord,
+    I: Iterator,
+    I::Item: Borrow<T>,
+{
+    fn peek(&mut self) -> Option<&T> {
+        self.next.as_ref().map(|item| item.borrow())
+    }
+
+    fn advance_past<Q>(&mut self, t: &Q) -> &mut Self
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.skip_before(|item| Borrow::<T>::borrow(item).borrow() <= t);
+        self
+    }
+
+    fn advance_until<Q>(&mut self, t: &Q) -> &mut Self
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.skip_before(|item| Borrow::<T>::borrow(item).borrow() < t);
+        self
+    }
+}
+
+impl<T, I> IterSetOperations<T> for Sorted<I>
+where
+    T: Ord,
+    I: Iterator,
+    I::Item: Borrow<T>,
+{
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T, I> ToList<'a, T> for Sorted<I>
+where
+    T: 'a + Ord + Clone,
+    I: Iterator<Item = &'a T>,
+{
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T, I> ToSet<'a, T> for Sorted<I>
+where
+    T: 'a + Ord + Clone,
+    I: Iterator<Item = &'a T>,
+{
+}
+
+impl<'a, T, I> Selection<'a, T> for Sorted<I>
+where
+    T: 'a + Ord + Clone,
+    I: Iterator<Item = &'a T>,
+{
+}
+
+impl<'a, K, V, I> SkipAheadMapIterator<'a, K, (&'a K, &'a V)> for Sorted<I>
+where
+    K: 'a + Ord,
+    V: 'a,
+    I: Iterator<Item = (&'a K, &'a V)>,
+{
+    fn advance_past_key<Q>(&mut self, key: &Q) -> &mut Self
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.skip_before(|(k, _)| (*k).borrow() <= key);
+        self
+    }
+
+    fn advance_until_key<Q>(&mut self, key: &Q) -> &mut Self
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.skip_before(|(k, _)| (*k).borrow() < key);
+        self
+    }
+
+    fn peek_key(&mut self) -> Option<&'a K> {
+        self.next.map(|(key, _)| key)
+    }
+}
+
+impl<'a, K, V, I> MapIterFilter<'a, K, V> for Sorted<I>
+where
+    K: 'a + Ord,
+    V: 'a,
+    I: Iterator<Item = (&'a K, &'a V)>,
+{
+}
+
+impl<'a, K, V, I> MapIterMerge<'a, K, V> for Sorted<I>
+where
+    K: 'a + Ord,
+    V: 'a,
+    I: Iterator<Item = (&'a K, &'a V)>,
+{
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, K, V, I> ToMap<'a, K, V> for Sorted<I>
+where
+    K: 'a + Ord + Clone,
+    V: 'a + Clone,
+    I: Iterator<Item = (&'a K, &'a V)>,
+{
+}
+
+// CHECKED ITERATOR ADAPTER
+
+// Whether `Checked` adapters verify the ordering of their items.
+const CHECK_ORDER: bool = cfg!(any(debug_assertions, feature = "checked"));
+
+/// An adapter that verifies that the output of a `SkipAheadIterator` is in strictly
+/// ascending order. It panics, naming the offending items, as soon as a pair of
+/// consecutive items is found to be out of order or equal.
+///
+/// To see every item, including those that an advance skips over, the adapter keeps
+/// the underlying iterator one item ahead of its own output and advances by stepping
+/// through the items rather than using the underlying iterator's advance methods.
+///
+/// The checks are made in builds with debug assertions enabled or when the `checked`
+/// feature is enabled. Otherwise the adapter just passes the iterator's output through.
+pub struct Checked<T, I: Iterator> {
+    iter: I,
+    head: Option<I::Item>,
+    filled: bool,
+    phantom: PhantomData<T>,
+}
+
+impl<T, I> Checked<T, I>
+where
+    T: Ord + fmt::Debug,
+    I: SkipAheadIterator<T>,
+    I::Item: Borrow<T>,
+{
+    /// Create a `Checked` adapter for `iter`.
+    pub fn new(iter: I) -> Self {
+        Self {
+            iter,
+            head: None,
+            filled: false,
+            phantom: PhantomData,
+        }
+    }
+
+    // Take the next item from the underlying iterator, unless that has already been
+    // done, and panic if it is not before the item that follows it.
+    fn fill(&mut self) {
+        if !self.filled {
+            self.head = self.iter.next();
+            self.filled = true;
+            if let (Some(item), Some(next)) = (&self.head, self.iter.peek()) {
+                match item.borrow().cmp(next) {
+                    Ordering::Less => (),
+                    Ordering::Equal => panic!("duplicate items: {:?}", next),
+                    Ordering::Greater => {
+                        panic!("items out of order: {:?} before {:?}", item.borrow(), next)
+                    }
+                }
+            }
+        }
+    }
+
+    // Step through the items until the next one is not before `t` (or equal to it if
+    // `inclusive` is false).
+    fn step_to<Q>(&mut self, t: &Q, inclusive: bool)
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        while let Some(item) = self.peek() {
+            match Ord::cmp(Borrow::<Q>::borrow(item), t) {
+                Ordering::Less => (),
+                Ordering::Equal if !inclusive => (),
+                _ => break,
+            }
+            self.next();
+        }
+    }
+}
+
+impl<T, I> Iterator for Checked<T, I>
+where
+    T: Ord + fmt::Debug,
+    I: SkipAheadIterator<T>,
+    I::Item: Borrow<T>,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if CHECK_ORDER {
+            self.fill();
+            self.filled = false;
+            self.head.take()
+        } else {
+            self.iter.next()
+        }
+    }
+}
+
+impl<T, I> SkipAheadIterator<T> for Checked<T, I>
+where
+    T: Ord + fmt::Debug,
+    I: SkipAheadIterator<T>,
+    I::Item: Borrow<T>,
+{
+    fn peek(&mut self) -> Option<&T> {
+        if CHECK_ORDER {
+            self.fill();
+            self.head.as_ref().map(|item| item.borrow())
+        } else {
+            self.iter.peek()
+        }
+    }
+
+    fn advance_past<Q>(&mut self, t: &Q) -> &mut Self
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        if CHECK_ORDER {
+            self.step_to(t, false);
+        } else {
+            self.iter.advance_past(t);
+        }
+        self
+    }
+
+    fn advance_until<Q>(&mut self, t: &Q) -> &mut Self
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        if CHECK_ORDER {
+            self.step_to(t, true);
+        } else {
+            self.iter.advance_until(t);
+        }
+        self
+    }
+}
+
+impl<T, I> IterSetOperations<T> for Checked<T, I>
+where
+    T: Ord + fmt::Debug,
+    I: SkipAheadIterator<T>,
+    I::Item: Borrow<T>,
+{
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T, I> ToList<'a, T> for Checked<T, I>
+where
+    T: 'a + Ord + Clone + fmt::Debug,
+    I: SkipAheadIterator<T, Item = &'a T>,
+{
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T, I> ToSet<'a, T> for Checked<T, I>
+where
+    T: 'a + Ord + Clone + fmt::Debug,
+    I: SkipAheadIterator<T, Item = &'a T>,
+{
+}
+
+impl<'a, T, I> Selection<'a, T> for Checked<T, I>
+where
+    T: 'a + Ord + Clone + fmt::Debug,
+    I: SkipAheadIterator<T, Item = &'a T>,
+{
+}
+
+// BTREESET RANGE ADAPTER
+
+/// A `SkipAheadIterator` over the items of a `BTreeSet` that fall within a range. The
+/// advance methods search the set for the target rather than stepping through the
+/// skipped items.
+#[cfg(feature = "alloc")]
+pub struct BTreeSetRange<'a, T: Ord> {
+    set: &'a BTreeSet<T>,
+    range: btree_set::Range<'a, T>,
+    next: Option<&'a T>,
+    last: Option<&'a T>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T: Ord> BTreeSetRange<'a, T> {
+    /// Create a `BTreeSetRange` visiting the items of `set` within `range`.
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as `BTreeSet::range`.
+    pub fn new<Q, R>(set: &'a BTreeSet<T>, range: R) -> Self
+    where
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+        T: Borrow<Q>,
+    {
+        let mut range = set.range(range);
+        let last = range.next_back();
+        let next = range.next().or(last);
+        Self {
+            set,
+            range,
+            next,
+            last,
+        }
+    }
+
+    // Start a new search of the set from `start` if it is after the next item. The last
+    // item in the range is held separately so the search ends before it.
+    fn seek<Q>(&mut self, start: Bound<&Q>)
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let (next, last) = match (self.next, self.last) {
+            (Some(next), Some(last)) => (next.borrow(), last.borrow()),
+            _ => return,
+        };
+        let (skip, exhausted) = match start {
+            Bound::Included(t) => (t > next, t > last),
+            Bound::Excluded(t) => (t >= next, t >= last),
+            Bound::Unbounded => (false, false),
+        };
+        if exhausted {
+            self.next = None;
+        } else if skip {
+            self.range = self.set.range::<Q, _>((start, Bound::Excluded(last)));
+            self.next = self.range.next().or(self.last);
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T: Ord> Iterator for BTreeSetRange<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.next?;
+        self.next = match self.last {
+            Some(last) if ptr::eq(next, last) => None,
+            _ => self.range.next().or(self.last),
+        };
+        Some(next)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T: Ord> SkipAheadIterator<T> for BTreeSetRange<'a, T> {
+    fn peek(&mut self) -> Option<&T> {
+        self.next
+    }
+
+    fn advance_past<Q>(&mut self, t: &Q) -> &mut Self
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.seek(Bound::Excluded(t));
+        self
+    }
+
+    fn advance_until<Q>(&mut self, t: &Q) -> &mut Self
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.seek(Bound::Included(t));
+        self
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T: Ord> IterSetOperations<T> for BTreeSetRange<'a, T> {}
+
+#[cfg(feature = "alloc")]
+impl<'a, T: Ord + Clone> ToList<'a, T> for BTreeSetRange<'a, T> {}
+
+#[cfg(feature = "alloc")]
+impl<'a, T: Ord + Clone> ToSet<'a, T> for BTreeSetRange<'a, T> {}
+
+#[cfg(feature = "alloc")]
+impl<'a, T: Ord + Clone> Selection<'a, T> for BTreeSetRange<'a, T> {}
+
+macro_rules! impl_op_for_btree_set_range {
+    ( $op:ident, $op_fn:ident, $output:ident, $method:ident, [ $( $item:tt )* ], $doc:meta ) => {
+        #[cfg(feature = "alloc")]
+        impl<'a, T, I> $op<I> for BTreeSetRange<'a, T>
+        where
+            T: Ord,
+            I: SkipAheadIterator<T, $( $item )*>,
+        {
+            type Output = $output<T, Self, I>;
+
+            #[$doc]
+            fn $op_fn(self, other: I) -> Self::Output {
+                self.$method(other)
+            }
+        }
+    };
+}
+
+impl_op_for_btree_set_range!(
+    BitOr,
+    bitor,
+    Union,
+    union,
+    [Item = &'a T],
+    doc = "Return a new ordered iterator over the set union of the contents
+    of this iterator and other."
+);
+impl_op_for_btree_set_range!(
+    BitAnd,
+    bitand,
+    Intersection,
+    intersection,
+    [],
+    doc = "Return a new ordered iterator over the set intersection of the contents
+    of this iterator and other."
+);
+impl_op_for_btree_set_range!(
+    BitXor,
+    bitxor,
+    SymmetricDifference,
+    symmetric_difference,
+    [Item = &'a T],
+    doc = "Return a new ordered iterator over the symmetric set difference
+    between the contents of this iterator and other."
+);
+impl_op_for_btree_set_range!(
+    Sub,
+    sub,
+    Difference,
+    difference,
+    [],
+    doc = "Return a new ordered iterator over the set difference of the contents
+    of this iterator and other."
+);
+
+// BTREEMAP RANGE ADAPTER
+
+/// A `SkipAheadMapIterator` over the key-value pairs of a `BTreeMap` whose keys fall
+/// within a range. The advance methods search the map for the target key rather than
+/// stepping through the skipped pairs.
+#[cfg(feature = "alloc")]
+pub struct BTreeMapRange<'a, K: Ord, V> {
+    map: &'a BTreeMap<K, V>,
+    range: btree_map::Range<'a, K, V>,
+    next: Option<(&'a K, &'a V)>,
+    last: Option<(&'a K, &'a V)>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, K: Ord, V> BTreeMapRange<'a, K, V> {
+    /// Create a `BTreeMapRange` visiting the pairs of `map` whose keys are within `range`.
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as `BTreeMap::range`.
+    pub fn new<Q, R>(map: &'a BTreeMap<K, V>, range: R) -> Self
+    where
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+        K: Borrow<Q>,
+    {
+        let mut range = map.range(range);
+        let last = range.next_back();
+        let next = range.next().or(last);
+        Self {
+            map,
+            range,
+            next,
+            last,
+        }
+    }
+
+    // Start a new search of the map from `start` if it is after the next key. The last
+    // pair in the range is held separately so the search ends before it.
+    fn seek<Q>(&mut self, start: Bound<&Q>)
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let (next, last) = match (self.next, self.last) {
+            (Some((next, _)), Some((last, _))) => (next.borrow(), last.borrow()),
+            _ => return,
+        };
+        let (skip, exhausted) = match start {
+            Bound::Included(k) => (k > next, k > last),
+            Bound::Excluded(k) => (k >= next, k >= last),
+            Bound::Unbounded => (false, false),
+        };
+        if exhausted {
+            self.next = None;
+        } else if skip {
+            self.range = self.map.range::<Q, _>((start, Bound::Excluded(last)));
+            self.next = self.range.next().or(self.last);
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, K: Ord, V> Iterator for BTreeMapRange<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.next?;
+        self.next = match self.last {
+            Some((last, _)) if ptr::eq(next.0, last) => None,
+            _ => self.range.next().or(self.last),
+        };
+        Some(next)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, K: Ord, V> SkipAheadMapIterator<'a, K, (&'a K, &'a V)> for BTreeMapRange<'a, K, V> {
+    fn advance_past_key<Q>(&mut self, key: &Q) -> &mut Self
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.seek(Bound::Excluded(key));
+        self
+    }
+
+    fn advance_until_key<Q>(&mut self, key: &Q) -> &mut Self
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.seek(Bound::Included(key));
+        self
+    }
+
+    fn peek_key(&mut self) -> Option<&'a K> {
+        self.next.map(|(key, _)| key)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, K: Ord, V> MapIterFilter<'a, K, V> for BTreeMapRange<'a, K, V> {}
+
+#[cfg(feature = "alloc")]
+impl<'a, K: Ord, V> MapIterMerge<'a, K, V> for BTreeMapRange<'a, K, V> {}
+
+#[cfg(feature = "alloc")]
+impl<'a, K: Ord + Clone, V: Clone> ToMap<'a, K, V> for BTreeMapRange<'a, K, V> {}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::ordered_map::ord_map_iterators::ToMap;
+    use crate::ordered_set::ord_set_iterators::ToList;
+    use crate::test_helpers::prelude::*;
+    use crate::{OrderedMap, OrderedSet};
+
+    #[test]
+    fn sorted_set_operations() {
+        let set: OrderedSet<u32> = (0..20).map(|x| x * 3).collect();
+        let evens = Sorted::new((0..30).map(|x| x * 2));
+        let common: Vec<u32> = evens.intersection(set.iter()).collect();
+        assert_eq!(common, vec![0, 6, 12, 18, 24, 30, 36, 42, 48, 54]);
+        let odds = Sorted::new((0..10).map(|x| x * 2 + 1));
+        assert_eq!((set.range(..10) - odds).to_list(), vec![0, 6]);
+        let lines = Sorted::new("apple\nfig\nkiwi\npear".lines());
+        let fruit: OrderedSet<&str> = vec!["fig", "lime", "pear"].into();
+        let found: Vec<&str> = lines.intersection(fruit.iter()).collect();
+        assert_eq!(found, vec!["fig", "pear"]);
+        let mut sorted = Sorted::new(set.iter());
+        assert_eq!(
+            SkipAheadIterator::<u32>::advance_past(&mut sorted, &20).peek(),
+            Some(&21)
+        );
+        assert_eq!(
+            SkipAheadIterator::<u32>::advance_until(&mut sorted, &30)
+                .to_list()
+                .len(),
+            10
+        );
+    }
+
+    // A `SkipAheadIterator` whose advance methods do nothing.
+    struct NoSkip<I: Iterator>(Sorted<I>);
+
+    impl<I: Iterator> Iterator for NoSkip<I> {
+        type Item = I::Item;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            self.0.next()
+        }
+    }
+
+    impl<I: Iterator<Item = u32>> SkipAheadIterator<u32> for NoSkip<I> {
+        fn peek(&mut self) -> Option<&u32> {
+            self.0.peek()
+        }
+
+        fn advance_past<Q>(&mut self, _: &Q) -> &mut Self
+        where
+            u32: Borrow<Q>,
+            Q: Ord + ?Sized,
+        {
+            self
+        }
+
+        fn advance_until<Q>(&mut self, _: &Q) -> &mut Self
+        where
+            u32: Borrow<Q>,
+            Q: Ord + ?Sized,
+        {
+            self
+        }
+    }
+
+    #[test]
+    fn checked_passes_ordered_items() {
+        let set: OrderedSet<u32> = (0..20).map(|x| x * 3).collect();
+        let checked = Checked::new(Sorted::new((0..30).map(|x| x * 2)));
+        assert_eq!(checked.intersection(set.iter()).count(), 10);
+        let mut checked = Checked::new(set.iter()).difference(Checked::new(set.range(10..)));
+        assert_eq!(checked.to_list(), vec![0, 3, 6, 9]);
+        let mut checked = Checked::new(set.iter());
+        assert_eq!(checked.advance_past(&30).peek(), Some(&33));
+        assert_eq!(checked.advance_until(&33).next(), Some(&33));
+        assert_eq!(checked.advance_until(&40).next(), Some(&42));
+        assert_eq!(checked.advance_past(&57).next(), None);
+    }
+
+    #[cfg(any(debug_assertions, feature = "checked"))]
+    #[test]
+    #[should_panic(expected = "items out of order: 7 before 5")]
+    fn checked_detects_unordered_items() {
+        let set: OrderedSet<u32> = (0..10).collect();
+        let unordered = Checked::new(Sorted::new(vec![1, 3, 7, 5, 9]));
+        let _ = unordered.union(Sorted::new(set.iter().cloned())).count();
+    }
+
+    #[cfg(any(debug_assertions, feature = "checked"))]
+    #[test]
+    #[should_panic(expected = "items out of order: 7 before 5")]
+    fn checked_detects_unordered_items_skipped_by_intersection() {
+        let set: OrderedSet<u32> = vec![5, 6].into();
+        let unordered = Checked::new(Sorted::new(vec![1, 3, 7, 5, 9]));
+        let _ = unordered.intersection(set.iter()).count();
+    }
+
+    #[cfg(any(debug_assertions, feature = "checked"))]
+    #[test]
+    #[should_panic(expected = "items out of order: 7 before 5")]
+    fn checked_detects_unordered_items_skipped_by_are_disjoint() {
+        let set: OrderedSet<u32> = vec![5, 6].into();
+        let unordered = Checked::new(Sorted::new(vec![1, 3, 7, 5, 9]));
+        crate::ordered_set::ord_set_iterators::are_disjoint(unordered, set.iter());
+    }
+
+    #[cfg(any(debug_assertions, feature = "checked"))]
+    #[test]
+    #[should_panic(expected = "duplicate items: 3")]
+    fn checked_detects_duplicate_items() {
+        let evens: OrderedSet<u32> = (0..5).map(|x| x * 2).collect();
+        let duplicated = Checked::new(Sorted::new(vec![1, 3, 3, 5]));
+        let _ = duplicated
+            .difference(Sorted::new(evens.iter().cloned()))
+            .count();
+    }
+
+    #[cfg(any(debug_assertions, feature = "checked"))]
+    #[test]
+    fn checked_steps_through_advances() {
+        let set: OrderedSet<u32> = vec![4, 6].into();
+        let no_skip = Checked::new(NoSkip(Sorted::new(vec![1, 2, 6])));
+        assert_eq!((set.iter() & no_skip).to_list(), vec![6]);
+    }
+
+    #[test]
+    fn btree_set_range() {
+        let btree: BTreeSet<u32> = (0..100).map(|x| x * 2).collect();
+        let set: OrderedSet<u32> = (0..50).map(|x| x * 5).collect();
+        let expected: Vec<u32> = (0..20).map(|x| x * 10).collect();
+        assert_eq!(
+            (set.iter() & BTreeSetRange::new(&btree, ..)).to_list(),
+            expected
+        );
+        assert_eq!(
+            (BTreeSetRange::new(&btree, 10..40) & set.iter()).to_list(),
+            vec![10, 20, 30]
+        );
+        assert_eq!(
+            (set.range(..20) | BTreeSetRange::new(&btree, 12..=16)).to_list(),
+            vec![0, 5, 10, 12, 14, 15, 16]
+        );
+        let mut range = BTreeSetRange::new(&btree, 10..=20);
+        assert_eq!(range.peek(), Some(&10));
+        assert_eq!(range.advance_past(&13).next(), Some(&14));
+        assert_eq!(range.advance_until(&18).peek(), Some(&18));
+        assert_eq!(range.advance_until(&15).peek(), Some(&18));
+        assert_eq!(range.advance_past(&19).peek(), Some(&20));
+        assert_eq!(range.next(), Some(&20));
+        assert_eq!(range.next(), None);
+        let mut range = BTreeSetRange::new(&btree, 10..=20);
+        assert_eq!(range.advance_past(&20).peek(), None);
+        assert_eq!(BTreeSetRange::new(&btree, 201..).next(), None);
+    }
+
+    #[test]
+    fn map_adapters() {
+        let btree: BTreeMap<u32, char> = (0..26).map(|x| (x, (b'a' + x as u8) as char)).collect();
+        let map: OrderedMap<u32, char> = vec![(30, 'x'), (31, 'y')].into_iter().collect();
+        let merged = BTreeMapRange::new(&btree, 20..).merge(map.iter()).to_map();
+        assert_eq!(merged.len(), 8);
+        assert_eq!(merged.get(&31), Some(&'y'));
+        let keys: OrderedSet<u32> = vec![3, 5, 7, 40].into();
+        let only: Vec<char> = BTreeMapRange::new(&btree, ..)
+            .only(keys.iter())
+            .map(|(_, v)| *v)
+            .collect();
+        assert_eq!(only, vec!['d', 'f', 'h']);
+        let except = Sorted::new(btree.range(..6))
+            .except(Sorted::new(1..5))
+            .to_map();
+        assert_eq!(except.keys().to_list(), vec![0, 5]);
+        let mut range = BTreeMapRange::new(&btree, 3..9);
+        assert_eq!(range.advance_until_key(&6).peek_key(), Some(&6));
+        assert_eq!(range.advance_past_key(&7).next(), Some((&8, &'i')));
+        assert_eq!(range.next(), None);
+    }
+}
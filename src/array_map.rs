@@ -0,0 +1,376 @@
+//! Fixed capacity maps implemented as a pair of sorted arrays.
+//! They never allocate which makes them suitable for `no_std` targets
+//! without a heap.
+
+use core::borrow::Borrow;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::mem;
+use core::ops::{Index, IndexMut};
+
+use crate::ordered_map::ord_map_iterators::{MapIter, MapIterMut, ValueIter, ValueIterMut};
+use crate::ordered_set::ord_set_iterators::SetIter;
+use crate::storage::{ArrayBuffer, CapacityError};
+
+/// A map of at most `N` entries ordered according to their keys' Ord
+/// held in inline arrays.
+pub struct ArrayOrderedMap<K: Ord, V, const N: usize> {
+    keys: ArrayBuffer<K, N>,
+    values: ArrayBuffer<V, N>,
+}
+
+impl<K: Ord, V, const N: usize> ArrayOrderedMap<K, V, N> {
+    pub fn new() -> Self {
+        Self {
+            keys: ArrayBuffer::new(),
+            values: ArrayBuffer::new(),
+        }
+    }
+
+    /// Create an `ArrayOrderedMap` from the key-value pairs in `iter` or return the first
+    /// pair that would not fit.
+    pub fn try_from_iter<I: IntoIterator<Item = (K, V)>>(
+        iter: I,
+    ) -> Result<Self, CapacityError<(K, V)>> {
+        let mut map = Self::new();
+        for (key, value) in iter {
+            map.insert(key, value)?;
+        }
+        Ok(map)
+    }
+
+    // Return true if keys is sorted and contains no duplicate keys
+    // and the same length as values.
+    #[cfg(test)]
+    pub(crate) fn is_valid(&self) -> bool {
+        let keys = self.keys.as_slice();
+        for i in 1..keys.len() {
+            if keys[i - 1] >= keys[i] {
+                return false;
+            }
+        }
+        keys.len() == self.values.as_slice().len()
+    }
+
+    /// Return the number of items in this map.
+    pub fn len(&self) -> usize {
+        self.keys.as_slice().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return `true` if no more keys can be inserted.
+    pub fn is_full(&self) -> bool {
+        self.keys.is_full()
+    }
+
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Removes all key-value pairs from the `ArrayOrderedMap`.
+    pub fn clear(&mut self) {
+        self.keys.clear();
+        self.values.clear();
+    }
+
+    /// Returns `true` if there is an entry for `key` in the map and `false` otherwise.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.index_of(key).is_ok()
+    }
+
+    fn index_of<Q>(&self, key: &Q) -> Result<usize, usize>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.keys
+            .as_slice()
+            .binary_search_by_key(&key, |x| x.borrow())
+    }
+
+    /// Returns an iterator visiting all key-value pairs in ascending order of their keys.
+    /// The iterator item type is `(&'a K, &'a V)`.
+    pub fn iter(&self) -> MapIter<'_, K, V> {
+        MapIter::new(self.keys.as_slice(), self.values.as_slice())
+    }
+
+    /// Returns an iterator visiting all key-value pairs in ascending order of their keys, with
+    /// mutable references to the values.
+    /// The iterator item type is `(&'a K, &'a mut V)`.
+    pub fn iter_mut(&mut self) -> MapIterMut<'_, K, V> {
+        MapIterMut::new(self.keys.as_slice(), self.values.as_mut_slice())
+    }
+
+    /// Returns an iterator visiting all key-value pairs whose key falls within the specified
+    /// range in ascending order of their keys.
+    /// The iterator item type is `(&'a K, &'a V)`.
+    pub fn range<Q, R>(&self, range: R) -> MapIter<'_, K, V>
+    where
+        Q: Ord + ?Sized,
+        R: core::ops::RangeBounds<Q>,
+        K: Borrow<Q>,
+    {
+        let keys = self.keys.as_slice();
+        let (start_index, end_index) = super::range_indices(keys, range);
+        MapIter::new(
+            &keys[start_index..end_index],
+            &self.values.as_slice()[start_index..end_index],
+        )
+    }
+
+    /// Returns an iterator visiting all key-value pairs whose key falls within the specified
+    /// range in ascending order of their keys, with mutable references to the values.
+    /// The iterator item type is `(&'a K, &'a mut V)`.
+    pub fn range_mut<Q, R>(&mut self, range: R) -> MapIterMut<'_, K, V>
+    where
+        Q: Ord + ?Sized,
+        R: core::ops::RangeBounds<Q>,
+        K: Borrow<Q>,
+    {
+        let keys = self.keys.as_slice();
+        let (start_index, end_index) = super::range_indices(keys, range);
+        MapIterMut::new(
+            &keys[start_index..end_index],
+            &mut self.values.as_mut_slice()[start_index..end_index],
+        )
+    }
+
+    /// Returns a `SetIter` iterator visiting all keys in the map in ascending order.
+    pub fn keys(&self) -> SetIter<'_, K> {
+        SetIter::new(self.keys.as_slice())
+    }
+
+    /// Returns an iterator visiting all values in the map in ascending order of their keys.
+    pub fn values(&self) -> ValueIter<'_, K, V> {
+        ValueIter::new(self.keys.as_slice(), self.values.as_slice())
+    }
+
+    /// Returns an iterator returning a mutable reference to all values in the map in
+    /// ascending order of their keys.
+    pub fn values_mut(&mut self) -> ValueIterMut<'_, K, V> {
+        ValueIterMut::new(self.keys.as_slice(), self.values.as_mut_slice())
+    }
+
+    /// Returns an immutable reference to the value in the map associated with `key` if
+    /// it exists and `None` otherwise.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let index = self.index_of(key).ok()?;
+        Some(&self.values.as_slice()[index])
+    }
+
+    /// Returns an mutable reference to the value in the map associated with `key` if
+    /// it exists and `None` otherwise.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let index = self.index_of(key).ok()?;
+        Some(&mut self.values.as_mut_slice()[index])
+    }
+
+    /// Inserts a key-value (`key`, `value`) pair into the map and returns the previous
+    /// value associated with `key` if it exists and `None` otherwise. If the map is full
+    /// and `key` is not already present the pair is returned in a `CapacityError`.
+    pub fn insert(&mut self, key: K, value: V) -> Result<Option<V>, CapacityError<(K, V)>> {
+        match self.index_of(&key) {
+            Ok(index) => Ok(Some(mem::replace(
+                &mut self.values.as_mut_slice()[index],
+                value,
+            ))),
+            Err(_) if self.is_full() => Err(CapacityError::new((key, value))),
+            Err(index) => {
+                // neither insertion can fail as the map is not full
+                let _ = self.keys.try_insert(index, key);
+                let _ = self.values.try_insert(index, value);
+                Ok(None)
+            }
+        }
+    }
+
+    /// Removes `key` from the map and returns the value associated with `key` in the
+    /// map if `key` existed in it and `None` otherwise.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.remove_entry(key).map(|(_, value)| value)
+    }
+
+    /// Removes `key` from the map and returns the key-value pair associated with `key` in the
+    /// map if `key` existed in it and `None` otherwise.
+    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let index = self.index_of(key).ok()?;
+        Some((self.keys.remove(index), self.values.remove(index)))
+    }
+}
+
+impl<K: Ord, V, const N: usize> Default for ArrayOrderedMap<K, V, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Ord + Clone, V: Clone, const N: usize> Clone for ArrayOrderedMap<K, V, N> {
+    fn clone(&self) -> Self {
+        Self {
+            keys: self.keys.clone(),
+            values: self.values.clone(),
+        }
+    }
+}
+
+impl<K: Ord + fmt::Debug, V: fmt::Debug, const N: usize> fmt::Debug for ArrayOrderedMap<K, V, N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: Ord, V: PartialEq, const N: usize> PartialEq for ArrayOrderedMap<K, V, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.keys.as_slice() == other.keys.as_slice()
+            && self.values.as_slice() == other.values.as_slice()
+    }
+}
+
+impl<K: Ord, V: Eq, const N: usize> Eq for ArrayOrderedMap<K, V, N> {}
+
+impl<K: Ord + Hash, V: Hash, const N: usize> Hash for ArrayOrderedMap<K, V, N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.keys.as_slice().hash(state);
+        self.values.as_slice().hash(state);
+    }
+}
+
+impl<K, V, Q, const N: usize> Index<&Q> for ArrayOrderedMap<K, V, N>
+where
+    K: Ord + Borrow<Q>,
+    Q: Ord + fmt::Debug + ?Sized,
+{
+    type Output = V;
+
+    /// Returns a reference to the value associated with `key`.
+    ///
+    /// # Panics
+    ///
+    /// Panics, naming `key` in the message, if `key` is not in the map.
+    fn index(&self, key: &Q) -> &Self::Output {
+        match self.get(key) {
+            Some(value) => value,
+            None => panic!("Unknown key {:?}", key),
+        }
+    }
+}
+
+impl<K, V, Q, const N: usize> IndexMut<&Q> for ArrayOrderedMap<K, V, N>
+where
+    K: Ord + Borrow<Q>,
+    Q: Ord + fmt::Debug + ?Sized,
+{
+    /// Returns a mutable reference to the value associated with `key`.
+    ///
+    /// # Panics
+    ///
+    /// Panics, naming `key` in the message, if `key` is not in the map.
+    fn index_mut(&mut self, key: &Q) -> &mut Self::Output {
+        match self.get_mut(key) {
+            Some(value) => value,
+            None => panic!("Unknown key {:?}", key),
+        }
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize> IntoIterator for &'a ArrayOrderedMap<K, V, N> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = MapIter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ordered_map::ord_map_iterators::{MapIterFilter, SkipAheadMapIterator};
+    use crate::test_helpers::prelude::*;
+
+    static TEST_ITEMS: &[(&str, u32)] = &[
+        ("hhh", 8),
+        ("aaa", 1),
+        ("zzz", 26),
+        ("ddd", 4),
+        ("bbb", 2),
+        ("qqq", 17),
+    ];
+
+    #[test]
+    fn array_map_insert_and_remove() {
+        let mut map = ArrayOrderedMap::<&str, u32, 5>::new();
+        for (key, value) in TEST_ITEMS[..5].iter() {
+            assert_eq!(map.insert(*key, *value), Ok(None));
+            assert!(map.is_valid());
+        }
+        assert!(map.is_full());
+        assert_eq!(map.insert("aaa", 10), Ok(Some(1)));
+        let (key, value) = TEST_ITEMS[5];
+        assert_eq!(
+            map.insert(key, value).map_err(|err| err.element()),
+            Err(("qqq", 17))
+        );
+        assert_eq!(
+            map.keys().cloned().collect::<Vec<_>>(),
+            vec!["aaa", "bbb", "ddd", "hhh", "zzz"]
+        );
+        assert_eq!(map["aaa"], 10);
+        map["bbb"] *= 3;
+        assert_eq!(map.get("bbb"), Some(&6));
+        assert_eq!(map.remove("ddd"), Some(4));
+        assert_eq!(map.remove("ddd"), None);
+        assert!(!map.contains_key("ddd"));
+        assert_eq!(map.insert(key, value), Ok(None));
+        assert!(map.is_valid());
+        let values: Vec<u32> = map.values().cloned().collect();
+        assert_eq!(values, vec![10, 6, 8, 17, 26]);
+        for value in map.values_mut() {
+            *value += 1;
+        }
+        let range: Vec<(&str, u32)> = map.range("c".."r").map(|(k, v)| (*k, *v)).collect();
+        assert_eq!(range, vec![("hhh", 9), ("qqq", 18)]);
+        assert!(
+            ArrayOrderedMap::<&str, u32, 5>::try_from_iter(TEST_ITEMS.iter().cloned()).is_err()
+        );
+        map.clear();
+        assert!(map.is_empty());
+    }
+
+    #[test]
+    fn array_map_iterators() {
+        let map =
+            ArrayOrderedMap::<&str, u32, 8>::try_from_iter(TEST_ITEMS.iter().cloned()).unwrap();
+        let other = ArrayOrderedMap::<&str, u32, 8>::try_from_iter(TEST_ITEMS[2..].iter().cloned())
+            .unwrap();
+        let mut iter = map.iter();
+        assert_eq!(iter.advance_past_key(&"ddd").peek_key(), Some(&"hhh"));
+        let only: Vec<&str> = map.iter().only(other.keys()).map(|(key, _)| *key).collect();
+        assert_eq!(only, vec!["bbb", "ddd", "qqq", "zzz"]);
+        assert_eq!(map.clone(), map);
+        assert_ne!(map, other);
+    }
+}
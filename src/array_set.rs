@@ -0,0 +1,389 @@
+//! Fixed capacity sets implemented as a sorted array.
+//! They never allocate which makes them suitable for `no_std` targets
+//! without a heap.
+
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::{BitAnd, BitOr, BitXor, Sub};
+
+use crate::ordered_set::ord_set_iterators::{
+    a_proper_superset_b, a_superset_b, are_disjoint, Difference, Intersection, SetIter,
+    SymmetricDifference, Union,
+};
+use crate::storage::{ArrayBuffer, ArrayIntoIter, CapacityError};
+
+/// A set of at most `N` items of type T ordered according to Ord (with no duplicates)
+/// held in an inline array.
+pub struct ArrayOrderedSet<T: Ord, const N: usize> {
+    members: ArrayBuffer<T, N>,
+}
+
+impl<T: Ord, const N: usize> ArrayOrderedSet<T, N> {
+    pub fn new() -> Self {
+        Self {
+            members: ArrayBuffer::new(),
+        }
+    }
+
+    /// Create an `ArrayOrderedSet` from the items in `iter` or return the first
+    /// item that would not fit.
+    pub fn try_from_iter<I: IntoIterator<Item = T>>(iter: I) -> Result<Self, CapacityError<T>> {
+        let mut set = Self::new();
+        for item in iter {
+            set.insert(item)?;
+        }
+        Ok(set)
+    }
+
+    // Build a set from the ascending and duplicate free output of `iter`.
+    fn try_from_ordered_iter<I: Iterator<Item = T>>(iter: I) -> Result<Self, CapacityError<T>> {
+        let mut set = Self::new();
+        for item in iter {
+            let index = set.len();
+            set.members
+                .try_insert(index, item)
+                .map_err(CapacityError::new)?;
+        }
+        Ok(set)
+    }
+
+    // Return true if members is sorted and contains no duplicates
+    #[cfg(test)]
+    pub(crate) fn is_valid(&self) -> bool {
+        let members = self.members.as_slice();
+        for i in 1..members.len() {
+            if members[i - 1] >= members[i] {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Return the number of items in this set.
+    pub fn len(&self) -> usize {
+        self.members.as_slice().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return `true` if no more items can be inserted.
+    pub fn is_full(&self) -> bool {
+        self.members.is_full()
+    }
+
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    pub fn clear(&mut self) {
+        self.members.clear()
+    }
+
+    /// Insert `item` in the set and return `Ok(false)` if the item was already a
+    /// member and `Ok(true)` otherwise. If the set is full and `item` is not a
+    /// member it is returned in a `CapacityError`.
+    pub fn insert(&mut self, item: T) -> Result<bool, CapacityError<T>> {
+        match self.members.as_slice().binary_search(&item) {
+            Ok(_) => Ok(false),
+            Err(index) => {
+                self.members
+                    .try_insert(index, item)
+                    .map_err(CapacityError::new)?;
+                Ok(true)
+            }
+        }
+    }
+
+    /// Remove `item` from the set and return `true`
+    /// if the item was a member and false otherwise
+    pub fn remove<Q>(&mut self, item: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        if let Ok(index) = self
+            .members
+            .as_slice()
+            .binary_search_by_key(&item, |x| x.borrow())
+        {
+            self.members.remove(index);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Return `true` if `item` is a member of the set
+    pub fn contains<Q>(&self, item: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.members
+            .as_slice()
+            .binary_search_by_key(&item, |x| x.borrow())
+            .is_ok()
+    }
+
+    /// Returns the set's first element in ascending order, or `None` if it is empty.
+    pub fn first(&self) -> Option<&T> {
+        self.members.as_slice().first()
+    }
+
+    /// Returns the set's last element in ascending order, or `None` if it is empty.
+    pub fn last(&self) -> Option<&T> {
+        self.members.as_slice().last()
+    }
+
+    /// Returns an iterator that iterates over the set's elements in ascending order
+    pub fn iter(&self) -> SetIter<'_, T> {
+        SetIter::new(self.members.as_slice())
+    }
+
+    /// Returns an iterator that iterates over the set's elements that fall within the
+    /// given range in ascending order
+    pub fn range<K, R>(&self, range: R) -> SetIter<'_, T>
+    where
+        K: Ord + ?Sized,
+        R: core::ops::RangeBounds<K>,
+        T: Borrow<K>,
+    {
+        let members = self.members.as_slice();
+        let (start_index, end_index) = super::range_indices(members, range);
+        SetIter::new(&members[start_index..end_index])
+    }
+
+    /// Iterate over the set union of this set and other in ascending order.
+    pub fn union<'a>(&'a self, other: &'a Self) -> Union<T, SetIter<'a, T>, SetIter<'a, T>> {
+        Union::new(self.iter(), other.iter())
+    }
+
+    /// Iterate over the set intersection of this set and other in ascending order.
+    pub fn intersection<'a>(
+        &'a self,
+        other: &'a Self,
+    ) -> Intersection<T, SetIter<'a, T>, SetIter<'a, T>> {
+        Intersection::new(self.iter(), other.iter())
+    }
+
+    /// Iterate over the set difference of this set and other in ascending order.
+    pub fn difference<'a>(
+        &'a self,
+        other: &'a Self,
+    ) -> Difference<T, SetIter<'a, T>, SetIter<'a, T>> {
+        Difference::new(self.iter(), other.iter())
+    }
+
+    /// Iterate over the symmetric set difference of this set and other in ascending order.
+    pub fn symmetric_difference<'a>(
+        &'a self,
+        other: &'a Self,
+    ) -> SymmetricDifference<T, SetIter<'a, T>, SetIter<'a, T>> {
+        SymmetricDifference::new(self.iter(), other.iter())
+    }
+
+    /// Return true if this set is disjoint from the other set
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        are_disjoint(self.iter(), other.iter())
+    }
+
+    /// Return true if self is a subset of other
+    pub fn is_subset(&self, other: &Self) -> bool {
+        a_superset_b(other.iter(), self.iter())
+    }
+
+    /// Return true if self is a proper subset of other
+    pub fn is_proper_subset(&self, other: &Self) -> bool {
+        a_proper_superset_b(other.iter(), self.iter())
+    }
+
+    /// Return true if self is a superset of other
+    pub fn is_superset(&self, other: &Self) -> bool {
+        a_superset_b(self.iter(), other.iter())
+    }
+
+    /// Return true if self is a proper superset of other
+    pub fn is_proper_superset(&self, other: &Self) -> bool {
+        a_proper_superset_b(self.iter(), other.iter())
+    }
+}
+
+impl<T: Ord, const N: usize> Default for ArrayOrderedSet<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord + Clone, const N: usize> Clone for ArrayOrderedSet<T, N> {
+    fn clone(&self) -> Self {
+        Self {
+            members: self.members.clone(),
+        }
+    }
+}
+
+impl<T: Ord + fmt::Debug, const N: usize> fmt::Debug for ArrayOrderedSet<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Ord, const N: usize> PartialEq for ArrayOrderedSet<T, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.members.as_slice() == other.members.as_slice()
+    }
+}
+
+impl<T: Ord, const N: usize> Eq for ArrayOrderedSet<T, N> {}
+
+impl<T: Ord, const N: usize> PartialOrd for ArrayOrderedSet<T, N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Ord, const N: usize> Ord for ArrayOrderedSet<T, N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.members.as_slice().cmp(other.members.as_slice())
+    }
+}
+
+impl<T: Ord + Hash, const N: usize> Hash for ArrayOrderedSet<T, N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.members.as_slice().hash(state)
+    }
+}
+
+impl<'a, T: Ord, const N: usize> IntoIterator for &'a ArrayOrderedSet<T, N> {
+    type Item = &'a T;
+    type IntoIter = SetIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: Ord, const N: usize> IntoIterator for ArrayOrderedSet<T, N> {
+    type Item = T;
+    type IntoIter = ArrayIntoIter<T, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.members.into_iter()
+    }
+}
+
+impl<T: Ord + Clone, const N: usize> BitAnd for &ArrayOrderedSet<T, N> {
+    type Output = ArrayOrderedSet<T, N>;
+
+    /// Apply the & operator to return a new set containing the intersection
+    /// of this set and other.
+    fn bitand(self, other: Self) -> Self::Output {
+        // the intersection is no bigger than self so cannot exceed capacity
+        ArrayOrderedSet::try_from_ordered_iter(self.intersection(other).cloned())
+            .unwrap_or_else(|_| unreachable!())
+    }
+}
+
+impl<T: Ord + Clone, const N: usize> Sub for &ArrayOrderedSet<T, N> {
+    type Output = ArrayOrderedSet<T, N>;
+
+    /// Apply the - operator to return a new set containing the set difference
+    /// between this set and other.
+    fn sub(self, other: Self) -> Self::Output {
+        // the difference is no bigger than self so cannot exceed capacity
+        ArrayOrderedSet::try_from_ordered_iter(self.difference(other).cloned())
+            .unwrap_or_else(|_| unreachable!())
+    }
+}
+
+impl<T: Ord + Clone, const N: usize> BitOr for &ArrayOrderedSet<T, N> {
+    type Output = Result<ArrayOrderedSet<T, N>, CapacityError<T>>;
+
+    /// Apply the | operator to return a new set containing the union of this set
+    /// and other or a `CapacityError` if the union has more than `N` members.
+    fn bitor(self, other: Self) -> Self::Output {
+        ArrayOrderedSet::try_from_ordered_iter(self.union(other).cloned())
+    }
+}
+
+impl<T: Ord + Clone, const N: usize> BitXor for &ArrayOrderedSet<T, N> {
+    type Output = Result<ArrayOrderedSet<T, N>, CapacityError<T>>;
+
+    /// Apply the ^ operator to return a new set containing the symmetric difference
+    /// of this set and other or a `CapacityError` if it has more than `N` members.
+    fn bitxor(self, other: Self) -> Self::Output {
+        ArrayOrderedSet::try_from_ordered_iter(self.symmetric_difference(other).cloned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ordered_set::ord_set_iterators::{IterSetOperations, SkipAheadIterator};
+    use crate::test_helpers::prelude::*;
+
+    static TEST_ITEMS: &[u32] = &[7, 3, 11, 5, 2, 13, 3, 17];
+
+    #[test]
+    fn array_set_insert_and_remove() {
+        let mut set = ArrayOrderedSet::<u32, 6>::new();
+        assert_eq!(set.capacity(), 6);
+        for item in TEST_ITEMS[..6].iter() {
+            assert_eq!(set.insert(*item), Ok(true));
+            assert!(set.is_valid());
+        }
+        assert!(set.is_full());
+        assert_eq!(set.insert(3), Ok(false));
+        assert_eq!(set.insert(17).map_err(|err| err.element()), Err(17));
+        assert_eq!(
+            set.iter().cloned().collect::<Vec<_>>(),
+            vec![2, 3, 5, 7, 11, 13]
+        );
+        assert!(set.remove(&5));
+        assert!(!set.remove(&5));
+        assert!(!set.contains(&5));
+        assert_eq!(set.insert(17), Ok(true));
+        assert!(set.is_valid());
+        assert_eq!(set.first(), Some(&2));
+        assert_eq!(set.last(), Some(&17));
+        assert_eq!(set.range(4..12).cloned().collect::<Vec<_>>(), vec![7, 11]);
+        assert!(ArrayOrderedSet::<u32, 6>::try_from_iter(TEST_ITEMS.iter().cloned()).is_err());
+        let items: Vec<u32> = set.clone().into_iter().collect();
+        assert_eq!(items, vec![2, 3, 7, 11, 13, 17]);
+        set.clear();
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn array_set_operations() {
+        let set1 = ArrayOrderedSet::<u32, 7>::try_from_iter(vec![1, 3, 5, 7, 9]).unwrap();
+        let set2 = ArrayOrderedSet::<u32, 7>::try_from_iter(vec![3, 4, 5, 6, 10]).unwrap();
+        assert_eq!(
+            (&set1 & &set2).iter().cloned().collect::<Vec<_>>(),
+            vec![3, 5]
+        );
+        assert_eq!(
+            (&set1 - &set2).iter().cloned().collect::<Vec<_>>(),
+            vec![1, 7, 9]
+        );
+        assert_eq!(
+            (&set1 ^ &set2).unwrap().iter().cloned().collect::<Vec<_>>(),
+            vec![1, 4, 6, 7, 9, 10]
+        );
+        assert!((&set1 | &set2).is_err());
+        assert_eq!(
+            set1.union(&set2).cloned().collect::<Vec<_>>(),
+            vec![1, 3, 4, 5, 6, 7, 9, 10]
+        );
+        let mut iter = set1.iter().intersection(set2.iter());
+        assert_eq!(iter.advance_past(&3).peek(), Some(&5));
+        assert!(set1.is_disjoint(&(&set2 - &set1)));
+        assert!((&set1 & &set2).is_proper_subset(&set1));
+        assert!(set2.is_superset(&(&set1 & &set2)));
+        assert_eq!((&set1 | &set1), Ok(set1.clone()));
+    }
+}
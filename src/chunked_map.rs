@@ -0,0 +1,812 @@
+//! Maps implemented as a sequence of sorted chunks of keys and values.
+//! Useful for those situations when the map is too large for insertion into a single
+//! sorted list to be efficient but ordered iteration over the map's contents is still
+//! a frequent requirement.
+//!
+//! As for `ChunkedOrderedSet`, the chunks of a map containing `n` items hold about `√n`
+//! items each so that insertion and removal are `O(√n)` (amortised) and lookup is
+//! `O(log n)`.
+
+use core::borrow::Borrow;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::iter::FromIterator;
+use core::ops::{BitOr, Bound, RangeBounds};
+use core::slice;
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::chunked_set::{chunk_sizes, needs_rechunk, preferred_chunk_len};
+use crate::ordered_map::ord_map_iterators::{
+    MapIter, MapIterFilter, MapIterMerge, MapIterMut, MapIterMutFilter, MapIterMutMerge,
+    MapMergeIter, MapMergeIterMut, SkipAheadMapIterator, ToMap,
+};
+use crate::ordered_set::ord_set_iterators::{
+    IterSetOperations, Selection, SkipAheadIterator, ToList, ToSet,
+};
+use crate::OrderedMap;
+
+/// A sorted (non empty) run of keys and their associated values.
+#[derive(Clone)]
+struct Chunk<K, V> {
+    keys: Vec<K>,
+    values: Vec<V>,
+}
+
+impl<K, V> Chunk<K, V> {
+    fn last_key(&self) -> &K {
+        // chunks are never empty
+        &self.keys[self.keys.len() - 1]
+    }
+
+    fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    fn split_off(&mut self, at: usize) -> Self {
+        Chunk {
+            keys: self.keys.split_off(at),
+            values: self.values.split_off(at),
+        }
+    }
+
+    fn append(&mut self, other: &mut Self) {
+        self.keys.append(&mut other.keys);
+        self.values.append(&mut other.values);
+    }
+
+    // Split the sorted `keys` and their `values` into chunks of (at most) `chunk_len` items.
+    fn split_into_chunks(keys: Vec<K>, values: Vec<V>, chunk_len: usize) -> Vec<Self> {
+        let mut keys = keys.into_iter();
+        let mut values = values.into_iter();
+        chunk_sizes(keys.len(), chunk_len)
+            .map(|size| Chunk {
+                keys: keys.by_ref().take(size).collect(),
+                values: values.by_ref().take(size).collect(),
+            })
+            .collect()
+    }
+}
+
+/// A map of keys of type K to values of type V ordered according to K's Ord implementation
+/// stored in a sequence of sorted chunks so that insertion and removal only move the
+/// items in a single chunk.
+#[derive(Clone)]
+pub struct ChunkedOrderedMap<K: Ord, V> {
+    chunks: Vec<Chunk<K, V>>,
+    len: usize,
+    chunk_len: usize,
+}
+
+impl<K: Ord, V> Default for ChunkedOrderedMap<K, V> {
+    fn default() -> Self {
+        Self {
+            chunks: vec![],
+            len: 0,
+            chunk_len: preferred_chunk_len(0),
+        }
+    }
+}
+
+impl<K: Ord, V> ChunkedOrderedMap<K, V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Return true if keys are sorted and contain no duplicate keys, no chunk is
+    // empty, the chunks (if there is more than one) are neither under nor over full
+    // and the recorded length is correct.
+    #[cfg(test)]
+    pub(crate) fn is_valid(&self) -> bool {
+        let mut len = 0;
+        for (i, chunk) in self.chunks.iter().enumerate() {
+            if chunk.keys.is_empty() || chunk.keys.len() != chunk.values.len() {
+                return false;
+            }
+            if chunk.len() > 2 * self.chunk_len
+                || (self.chunks.len() > 1 && chunk.len() < self.chunk_len / 2)
+            {
+                return false;
+            }
+            if i > 0 && self.chunks[i - 1].last_key() >= &chunk.keys[0] {
+                return false;
+            }
+            for j in 1..chunk.keys.len() {
+                if chunk.keys[j - 1] >= chunk.keys[j] {
+                    return false;
+                }
+            }
+            len += chunk.keys.len();
+        }
+        len == self.len
+    }
+
+    /// Return the number of items in this map.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Removes all key-value pairs from the `ChunkedOrderedMap`.
+    pub fn clear(&mut self) {
+        self.chunks.clear();
+        self.len = 0;
+        self.chunk_len = preferred_chunk_len(0);
+    }
+
+    // Split the chunk at `index` in two if it has grown too long.
+    fn split_chunk_if_full(&mut self, index: usize) {
+        let chunk = &mut self.chunks[index];
+        if chunk.len() > 2 * self.chunk_len {
+            let tail = chunk.split_off(chunk.len() / 2);
+            self.chunks.insert(index + 1, tail);
+        }
+    }
+
+    // Merge the chunk at `index` with a neighbour if it has become too short.
+    fn merge_chunk_if_short(&mut self, index: usize) {
+        if self.chunks[index].keys.is_empty() {
+            self.chunks.remove(index);
+        } else if self.chunks.len() > 1 && self.chunks[index].len() < self.chunk_len / 2 {
+            let index = if index + 1 < self.chunks.len() {
+                index
+            } else {
+                index - 1
+            };
+            let mut next = self.chunks.remove(index + 1);
+            self.chunks[index].append(&mut next);
+            self.split_chunk_if_full(index);
+        }
+    }
+
+    // Redistribute the items between chunks of the preferred length if the length of
+    // the map has changed enough since the chunk length was last chosen.
+    fn rechunk_if_needed(&mut self) {
+        if needs_rechunk(self.chunk_len, self.len) {
+            self.chunk_len = preferred_chunk_len(self.len);
+            let mut keys = Vec::with_capacity(self.len);
+            let mut values = Vec::with_capacity(self.len);
+            for mut chunk in self.chunks.drain(..) {
+                keys.append(&mut chunk.keys);
+                values.append(&mut chunk.values);
+            }
+            self.chunks = Chunk::split_into_chunks(keys, values, self.chunk_len);
+        }
+    }
+
+    // Return the index of the chunk that would contain `key` if it were present.
+    fn chunk_index<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        if self.chunks.is_empty() {
+            None
+        } else {
+            let index = self
+                .chunks
+                .partition_point(|chunk| chunk.last_key().borrow() < key);
+            Some(index.min(self.chunks.len() - 1))
+        }
+    }
+
+    // Return the (chunk, offset) position of the first key that is greater than (or equal
+    // to if `inclusive` is true) `key`.
+    fn position<Q>(&self, key: &Q, inclusive: bool) -> (usize, usize)
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let before = |k: &K| {
+            if inclusive {
+                k.borrow() < key
+            } else {
+                k.borrow() <= key
+            }
+        };
+        let chunk_index = self
+            .chunks
+            .partition_point(|chunk| before(chunk.last_key()));
+        if let Some(chunk) = self.chunks.get(chunk_index) {
+            (chunk_index, chunk.keys.partition_point(|k| before(k)))
+        } else {
+            (chunk_index, 0)
+        }
+    }
+
+    /// Returns `true` if there is an entry for `key` in the `ChunkedOrderedMap` and `false` otherwise.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.get(key).is_some()
+    }
+
+    /// Returns an immutable reference to the value in the `ChunkedOrderedMap` associated
+    /// with `key` if it exists and `None` otherwise.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let chunk = &self.chunks[self.chunk_index(key)?];
+        match chunk.keys.binary_search_by_key(&key, |x| x.borrow()) {
+            Ok(index) => Some(&chunk.values[index]),
+            Err(_) => None,
+        }
+    }
+
+    /// Returns an mutable reference to the value in the `ChunkedOrderedMap` associated
+    /// with `key` if it exists and `None` otherwise.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let chunk_index = self.chunk_index(key)?;
+        let chunk = &mut self.chunks[chunk_index];
+        match chunk.keys.binary_search_by_key(&key, |x| x.borrow()) {
+            Ok(index) => Some(&mut chunk.values[index]),
+            Err(_) => None,
+        }
+    }
+
+    /// Inserts a key-value (`key`, `value`) pair into the `ChunkedOrderedMap` and returns
+    /// the previous value associated with `key` if it exists and `None` otherwise.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let chunk_index = match self.chunk_index(&key) {
+            Some(chunk_index) => chunk_index,
+            None => {
+                self.chunks.push(Chunk {
+                    keys: vec![key],
+                    values: vec![value],
+                });
+                self.len = 1;
+                return None;
+            }
+        };
+        let chunk = &mut self.chunks[chunk_index];
+        match chunk.keys.binary_search(&key) {
+            Ok(index) => Some(core::mem::replace(&mut chunk.values[index], value)),
+            Err(index) => {
+                chunk.keys.insert(index, key);
+                chunk.values.insert(index, value);
+                self.len += 1;
+                self.split_chunk_if_full(chunk_index);
+                self.rechunk_if_needed();
+                None
+            }
+        }
+    }
+
+    /// Removes `key` from the `ChunkedOrderedMap` and returns the value associated with
+    /// `key` if `key` existed in it and `None` otherwise.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let chunk_index = self.chunk_index(key)?;
+        let chunk = &mut self.chunks[chunk_index];
+        let index = chunk.keys.binary_search_by_key(&key, |x| x.borrow()).ok()?;
+        chunk.keys.remove(index);
+        let value = chunk.values.remove(index);
+        self.len -= 1;
+        self.merge_chunk_if_short(chunk_index);
+        self.rechunk_if_needed();
+        Some(value)
+    }
+
+    /// Returns an iterator visiting all key-value pairs in ascending order of their keys.
+    /// The iterator item type is `(&'a K, &'a V)`.
+    pub fn iter(&self) -> ChunkedMapIter<'_, K, V> {
+        let end_offset = self.chunks.last().map_or(0, |chunk| chunk.keys.len());
+        ChunkedMapIter::new(&self.chunks, 0, end_offset)
+    }
+
+    /// Returns an iterator visiting all key-value pairs in ascending order of their keys, with
+    /// mutable references to the values.
+    /// The iterator item type is `(&'a K, &'a mut V)`.
+    pub fn iter_mut(&mut self) -> ChunkedMapIterMut<'_, K, V> {
+        ChunkedMapIterMut {
+            chunks: self.chunks.iter_mut(),
+            iter: MapIterMut::new(&[], &mut []),
+        }
+    }
+
+    /// Returns an iterator visiting all key-value pairs whose key falls within the specified
+    /// range in ascending order of their keys.
+    /// The iterator item type is `(&'a K, &'a V)`.
+    pub fn range<Q, R>(&self, range: R) -> ChunkedMapIter<'_, K, V>
+    where
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+        K: Borrow<Q>,
+    {
+        crate::check_range_bounds(&range);
+        let (start_chunk, start_offset) = match range.start_bound() {
+            Bound::Unbounded => (0, 0),
+            Bound::Included(key) => self.position(key, true),
+            Bound::Excluded(key) => self.position(key, false),
+        };
+        let (end_chunk, end_offset) = match range.end_bound() {
+            Bound::Unbounded => (self.chunks.len(), 0),
+            Bound::Included(key) => self.position(key, false),
+            Bound::Excluded(key) => self.position(key, true),
+        };
+        if (start_chunk, start_offset) >= (end_chunk, end_offset) {
+            ChunkedMapIter::new(&[], 0, 0)
+        } else if end_offset == 0 {
+            let chunks = &self.chunks[start_chunk..end_chunk];
+            let end_offset = chunks.last().map_or(0, |chunk| chunk.keys.len());
+            ChunkedMapIter::new(chunks, start_offset, end_offset)
+        } else {
+            ChunkedMapIter::new(
+                &self.chunks[start_chunk..=end_chunk],
+                start_offset,
+                end_offset,
+            )
+        }
+    }
+
+    /// Returns a `SkipAheadIterator` visiting all keys in the `ChunkedOrderedMap` in
+    /// ascending order.
+    pub fn keys(&self) -> ChunkedKeyIter<'_, K, V> {
+        ChunkedKeyIter { iter: self.iter() }
+    }
+
+    /// Returns an iterator visiting all values in the `ChunkedOrderedMap` in ascending
+    /// order of their keys.
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.chunks.iter().flat_map(|chunk| chunk.values.iter())
+    }
+
+    /// Returns an iterator returning a mutable reference to all values in the
+    /// `ChunkedOrderedMap` in ascending order of their keys.
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut V> {
+        self.chunks
+            .iter_mut()
+            .flat_map(|chunk| chunk.values.iter_mut())
+    }
+}
+
+// Equality, hashing and formatting depend only on the contents of the map and not on
+// how those contents happen to be distributed between chunks.
+impl<K: Ord, V: PartialEq> PartialEq for ChunkedOrderedMap<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<K: Ord, V: Eq> Eq for ChunkedOrderedMap<K, V> {}
+
+impl<K: Ord + Hash, V: Hash> Hash for ChunkedOrderedMap<K, V> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len);
+        for (key, value) in self.iter() {
+            key.hash(state);
+            value.hash(state);
+        }
+    }
+}
+
+impl<K: Ord + fmt::Debug, V: fmt::Debug> fmt::Debug for ChunkedOrderedMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: Ord, V> From<OrderedMap<K, V>> for ChunkedOrderedMap<K, V> {
+    fn from(map: OrderedMap<K, V>) -> Self {
+        let len = map.keys.len();
+        let chunk_len = preferred_chunk_len(len);
+        Self {
+            chunks: Chunk::split_into_chunks(map.keys, map.values, chunk_len),
+            len,
+            chunk_len,
+        }
+    }
+}
+
+impl<K: Ord, V> From<ChunkedOrderedMap<K, V>> for OrderedMap<K, V> {
+    fn from(map: ChunkedOrderedMap<K, V>) -> Self {
+        let mut keys = Vec::with_capacity(map.len);
+        let mut values = Vec::with_capacity(map.len);
+        for mut chunk in map.chunks {
+            keys.append(&mut chunk.keys);
+            values.append(&mut chunk.values);
+        }
+        Self { keys, values }
+    }
+}
+
+impl<K: Ord + Clone, V: Clone> FromIterator<(K, V)> for ChunkedOrderedMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        OrderedMap::from_iter(iter).into()
+    }
+}
+
+impl<'a, K: Ord, V> IntoIterator for &'a ChunkedOrderedMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = ChunkedMapIter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+// CHUNKED MAP ITERATOR
+
+/// An Iterator over the items in a `ChunkedOrderedMap`
+pub struct ChunkedMapIter<'a, K: Ord, V> {
+    chunks: &'a [Chunk<K, V>],
+    start_offset: usize,
+    end_offset: usize,
+    chunk_index: usize,
+    iter: MapIter<'a, K, V>,
+}
+
+impl<'a, K: Ord, V> ChunkedMapIter<'a, K, V> {
+    fn new(chunks: &'a [Chunk<K, V>], start_offset: usize, end_offset: usize) -> Self {
+        let mut iter = Self {
+            chunks,
+            start_offset,
+            end_offset,
+            chunk_index: 0,
+            iter: MapIter::new(&[], &[]),
+        };
+        iter.iter = iter.chunk_iter(0);
+        iter
+    }
+
+    // Return a `MapIter` over the part of the indexed chunk that is within range.
+    fn chunk_iter(&self, chunk_index: usize) -> MapIter<'a, K, V> {
+        let chunks = self.chunks;
+        if let Some(chunk) = chunks.get(chunk_index) {
+            let start = if chunk_index == 0 {
+                self.start_offset
+            } else {
+                0
+            };
+            let end = if chunk_index + 1 == chunks.len() {
+                self.end_offset
+            } else {
+                chunk.keys.len()
+            };
+            MapIter::new(&chunk.keys[start..end], &chunk.values[start..end])
+        } else {
+            MapIter::new(&[], &[])
+        }
+    }
+
+    // Move to the first chunk (at or after the current chunk) whose last key is not
+    // before the target key.
+    fn skip_chunks<F: Fn(&K) -> bool>(&mut self, before: F) {
+        let skip = self.chunks[self.chunk_index.min(self.chunks.len())..]
+            .partition_point(|chunk| before(chunk.last_key()));
+        if skip > 0 {
+            self.chunk_index += skip;
+            self.iter = self.chunk_iter(self.chunk_index);
+        }
+    }
+}
+
+impl<'a, K: Ord, V> Iterator for ChunkedMapIter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.peek_key()?;
+        self.iter.next()
+    }
+}
+
+impl<'a, K: 'a + Ord, V: 'a> SkipAheadMapIterator<'a, K, (&'a K, &'a V)>
+    for ChunkedMapIter<'a, K, V>
+{
+    fn advance_past_key<Q>(&mut self, key: &Q) -> &mut Self
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.skip_chunks(|k| k.borrow() <= key);
+        self.iter.advance_past_key(key);
+        self
+    }
+
+    fn advance_until_key<Q>(&mut self, key: &Q) -> &mut Self
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.skip_chunks(|k| k.borrow() < key);
+        self.iter.advance_until_key(key);
+        self
+    }
+
+    fn peek_key(&mut self) -> Option<&'a K> {
+        loop {
+            if let Some(key) = self.iter.peek_key() {
+                return Some(key);
+            } else if self.chunk_index + 1 < self.chunks.len() {
+                self.chunk_index += 1;
+                self.iter = self.chunk_iter(self.chunk_index);
+            } else {
+                return None;
+            }
+        }
+    }
+}
+
+impl<'a, K: Ord + Clone, V: Clone> ToMap<'a, K, V> for ChunkedMapIter<'a, K, V> {}
+
+impl<'a, K: Ord, V> MapIterFilter<'a, K, V> for ChunkedMapIter<'a, K, V> {}
+
+impl<'a, K: 'a + Ord + Clone, V: 'a> MapIterMerge<'a, K, V> for ChunkedMapIter<'a, K, V> {}
+
+impl<'a, K, V, I> BitOr<I> for ChunkedMapIter<'a, K, V>
+where
+    K: 'a + Ord + Clone,
+    V: 'a + Clone,
+    I: SkipAheadMapIterator<'a, K, (&'a K, &'a V)>,
+{
+    type Output = MapMergeIter<'a, K, V, Self, I>;
+
+    /// Apply | operator and return a new ordered iterator over the
+    /// contents of this iterator and other.
+    fn bitor(self, other: I) -> Self::Output {
+        self.merge(other)
+    }
+}
+
+// MUTABLE CHUNKED MAP ITERATOR
+
+/// An Iterator over the keys and mutable values in a `ChunkedOrderedMap` in key order
+pub struct ChunkedMapIterMut<'a, K: Ord, V> {
+    chunks: slice::IterMut<'a, Chunk<K, V>>,
+    iter: MapIterMut<'a, K, V>,
+}
+
+impl<'a, K: Ord, V> ChunkedMapIterMut<'a, K, V> {
+    // Make the next chunk current and return false if there are no more chunks.
+    fn next_chunk(&mut self, skip: usize) -> bool {
+        if let Some(chunk) = self.chunks.nth(skip) {
+            self.iter = MapIterMut::new(&chunk.keys, &mut chunk.values);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+impl<'a, K: Ord, V> Iterator for ChunkedMapIterMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.peek_key()?;
+        self.iter.next()
+    }
+}
+
+impl<'a, K: 'a + Ord, V: 'a> SkipAheadMapIterator<'a, K, (&'a K, &'a mut V)>
+    for ChunkedMapIterMut<'a, K, V>
+{
+    fn advance_past_key<Q>(&mut self, key: &Q) -> &mut Self
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.iter.advance_past_key(key);
+        if self.iter.peek_key().is_none() {
+            let skip = self
+                .chunks
+                .as_slice()
+                .partition_point(|chunk| chunk.last_key().borrow() <= key);
+            if self.next_chunk(skip) {
+                self.iter.advance_past_key(key);
+            }
+        }
+        self
+    }
+
+    fn advance_until_key<Q>(&mut self, key: &Q) -> &mut Self
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.iter.advance_until_key(key);
+        if self.iter.peek_key().is_none() {
+            let skip = self
+                .chunks
+                .as_slice()
+                .partition_point(|chunk| chunk.last_key().borrow() < key);
+            if self.next_chunk(skip) {
+                self.iter.advance_until_key(key);
+            }
+        }
+        self
+    }
+
+    fn peek_key(&mut self) -> Option<&'a K> {
+        loop {
+            if let Some(key) = self.iter.peek_key() {
+                return Some(key);
+            } else if !self.next_chunk(0) {
+                return None;
+            }
+        }
+    }
+}
+
+impl<'a, K: Ord, V> MapIterMutFilter<'a, K, V> for ChunkedMapIterMut<'a, K, V> {}
+
+impl<'a, K: 'a + Ord + Clone, V: 'a> MapIterMutMerge<'a, K, V> for ChunkedMapIterMut<'a, K, V> {}
+
+impl<'a, K, V, I> BitOr<I> for ChunkedMapIterMut<'a, K, V>
+where
+    K: 'a + Ord + Clone,
+    V: 'a + Clone,
+    I: SkipAheadMapIterator<'a, K, (&'a K, &'a mut V)>,
+{
+    type Output = MapMergeIterMut<'a, K, V, Self, I>;
+
+    /// Apply | operator and return a new ordered iterator over the
+    /// contents of this iterator and other.
+    fn bitor(self, other: I) -> Self::Output {
+        self.merge(other)
+    }
+}
+
+// CHUNKED KEY ITERATOR
+
+/// An Iterator over the keys in a `ChunkedOrderedMap`
+pub struct ChunkedKeyIter<'a, K: Ord, V> {
+    iter: ChunkedMapIter<'a, K, V>,
+}
+
+impl<'a, K: Ord, V> Iterator for ChunkedKeyIter<'a, K, V> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(key, _)| key)
+    }
+}
+
+impl<'a, K: 'a + Ord, V: 'a> SkipAheadIterator<K> for ChunkedKeyIter<'a, K, V> {
+    fn peek(&mut self) -> Option<&'a K> {
+        self.iter.peek_key()
+    }
+
+    fn advance_past<Q>(&mut self, key: &Q) -> &mut Self
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.iter.advance_past_key(key);
+        self
+    }
+
+    fn advance_until<Q>(&mut self, key: &Q) -> &mut Self
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.iter.advance_until_key(key);
+        self
+    }
+}
+
+impl<'a, K: Ord + Clone, V> ToList<'a, K> for ChunkedKeyIter<'a, K, V> {}
+
+impl<'a, K: Ord + Clone, V> ToSet<'a, K> for ChunkedKeyIter<'a, K, V> {}
+
+impl<'a, K: Ord + Clone, V> IterSetOperations<K> for ChunkedKeyIter<'a, K, V> {}
+
+impl<'a, K: Ord + Clone, V> Selection<'a, K> for ChunkedKeyIter<'a, K, V> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ordered_set::ord_set_iterators::SetIter;
+    use crate::test_helpers::random_sequence_below;
+
+    #[test]
+    fn chunked_map_insert_and_remove() {
+        let mut map = ChunkedOrderedMap::<u64, u64>::new();
+        let mut ref_map = OrderedMap::<u64, u64>::new();
+        for (i, key) in random_sequence_below(5000, 100_000).into_iter().enumerate() {
+            assert_eq!(map.insert(key, i as u64), ref_map.insert(key, i as u64));
+        }
+        assert!(map.is_valid());
+        assert!(map.chunks.len() > 1);
+        assert_eq!(map.len(), ref_map.len());
+        assert_eq!(map.iter().to_map(), ref_map);
+        for key in random_sequence_below(2500, 100_000) {
+            assert_eq!(map.remove(&key), ref_map.remove(&key));
+            assert_eq!(map.get(&key), None);
+        }
+        assert!(map.is_valid());
+        for key in ref_map.keys() {
+            assert_eq!(map.get(key), ref_map.get(key));
+        }
+        for value in map.values_mut() {
+            *value += 1;
+        }
+        for (key, value) in ref_map.iter() {
+            assert_eq!(map.get(key), Some(&(value + 1)));
+        }
+        assert_eq!(map.values().count(), ref_map.len());
+    }
+
+    #[test]
+    fn chunked_map_equality_ignores_chunk_layout() {
+        use std::collections::hash_map::DefaultHasher;
+
+        let collected: ChunkedOrderedMap<u64, u64> = (0..600).map(|x| (x, x * 2)).collect();
+        let mut inserted = ChunkedOrderedMap::<u64, u64>::new();
+        for key in 0..600 {
+            inserted.insert(key, key * 2);
+        }
+        assert_ne!(collected.chunks.len(), inserted.chunks.len());
+        assert_eq!(collected, inserted);
+        let hash = |map: &ChunkedOrderedMap<u64, u64>| {
+            let mut hasher = DefaultHasher::new();
+            map.hash(&mut hasher);
+            hasher.finish()
+        };
+        assert_eq!(hash(&collected), hash(&inserted));
+        assert_eq!(format!("{:?}", collected), format!("{:?}", inserted));
+        inserted.insert(300, 0);
+        assert_ne!(collected, inserted);
+    }
+
+    #[test]
+    fn chunked_map_range() {
+        let map: ChunkedOrderedMap<u64, u64> = (0..5000).map(|x| (x * 2, x)).collect();
+        assert!(map.is_valid());
+        let ref_map = OrderedMap::from(map.clone());
+        for (start, end) in &[(0, 1), (511, 513), (250, 1000), (1023, 1025), (1, 9999)] {
+            assert!(map.range(start..end).eq(ref_map.range(start..end)));
+            assert!(map.range(start..=end).eq(ref_map.range(start..=end)));
+        }
+        assert_eq!(map.range(..).count(), 5000);
+        assert_eq!(map.range(200..200).count(), 0);
+        assert_eq!(map.range(20000..).count(), 0);
+    }
+
+    #[test]
+    fn chunked_map_skip_ahead() {
+        let mut map: ChunkedOrderedMap<u64, u64> = (0..5000).map(|x| (x * 2, x)).collect();
+        let mut iter = map.iter();
+        assert_eq!(iter.advance_until_key(&2501).next(), Some((&2502, &1251)));
+        assert_eq!(iter.advance_past_key(&7500).next(), Some((&7502, &3751)));
+        assert_eq!(iter.advance_past_key(&50000).next(), None);
+        let filtered = map.iter().only(SetIter::new(&[4, 5, 3000, 9998])).to_map();
+        assert_eq!(filtered.keys().to_list(), vec![4, 3000, 9998]);
+        let other: OrderedMap<u64, u64> = (0..10).map(|x| (x * 2 + 1, x)).collect();
+        let merged = (map.range(..20) | other.iter()).to_map();
+        assert!(merged.is_valid());
+        assert_eq!(merged.len(), 20);
+        assert!(map.keys().intersection(other.keys()).to_list().is_empty());
+        let mut iter = map.iter_mut();
+        assert_eq!(
+            iter.advance_until_key(&2501).next(),
+            Some((&2502, &mut 1251))
+        );
+        assert_eq!(
+            iter.advance_past_key(&7500).next(),
+            Some((&7502, &mut 3751))
+        );
+        assert_eq!(iter.advance_past_key(&50000).next(), None);
+        for (_, value) in map.iter_mut().only(SetIter::new(&[4, 3000, 9998])) {
+            *value = 0;
+        }
+        assert_eq!(map.get(&3000), Some(&0));
+        assert_eq!(map.get(&3002), Some(&1501));
+        assert_eq!(map.iter_mut().count(), 5000);
+    }
+}
@@ -0,0 +1,681 @@
+//! Sets implemented as a sequence of sorted chunks.
+//! Useful for those situations when the set is too large for insertion
+//! into a single sorted list to be efficient but ordered iteration over
+//! the set's contents is still a frequent requirement.
+//!
+//! The chunks of a set containing `n` items hold about `√n` items each so that both
+//! the number of chunks and the number of items in a chunk are `O(√n)`. Insertion and
+//! removal are `O(√n)` (amortised over the occasional re-chunking of the whole set as
+//! it grows or shrinks) and lookup is `O(log n)`.
+
+use core::borrow::Borrow;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::iter::FromIterator;
+use core::ops::{BitAnd, BitOr, BitXor, Bound, RangeBounds, Sub};
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::ordered_set::ord_set_iterators::{
+    a_superset_b, are_disjoint, Difference, Intersection, IterSetOperations, Selection, SetIter,
+    SkipAheadIterator, SymmetricDifference, ToList, ToSet, Union,
+};
+use crate::OrderedSet;
+
+// The smallest chunk length that will be used however few items there are.
+const MIN_CHUNK_LEN: usize = 16;
+
+// Return the preferred chunk length (about √len) for a collection of `len` items.
+pub(crate) fn preferred_chunk_len(len: usize) -> usize {
+    // integer square root by Newton's method
+    let mut root = len;
+    if len > 1 {
+        let mut next = (root + len / root) / 2;
+        while next < root {
+            root = next;
+            next = (root + len / root) / 2;
+        }
+    }
+    root.max(MIN_CHUNK_LEN)
+}
+
+// Return true if `chunk_len` differs from the preferred chunk length for `len` items by
+// a factor of two or more. As `√len` only doubles (or halves) after `len` has quadrupled
+// (or fallen to a quarter) re-chunking when this happens costs `O(1)` amortised per
+// insertion or removal.
+pub(crate) fn needs_rechunk(chunk_len: usize, len: usize) -> bool {
+    let preferred = preferred_chunk_len(len);
+    preferred >= 2 * chunk_len || 2 * preferred <= chunk_len
+}
+
+// Return the sizes of the chunks that `len` items should be split into so that each chunk
+// has (as near as possible) the same length and none is longer than `chunk_len`.
+pub(crate) fn chunk_sizes(len: usize, chunk_len: usize) -> impl Iterator<Item = usize> {
+    let count = len.div_ceil(chunk_len);
+    (0..count).map(move |i| len * (i + 1) / count - len * i / count)
+}
+
+/// An set of items of type T ordered according to Ord (with no duplicates) stored
+/// in a sequence of sorted chunks so that insertion and removal only move the
+/// elements in a single chunk.
+#[derive(Clone)]
+pub struct ChunkedOrderedSet<T: Ord> {
+    chunks: Vec<Vec<T>>,
+    len: usize,
+    chunk_len: usize,
+}
+
+impl<T: Ord> Default for ChunkedOrderedSet<T> {
+    fn default() -> Self {
+        Self {
+            chunks: vec![],
+            len: 0,
+            chunk_len: preferred_chunk_len(0),
+        }
+    }
+}
+
+impl<T: Ord> ChunkedOrderedSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Return true if members are sorted and contain no duplicates, no chunk is
+    // empty, the chunks (if there is more than one) are neither under nor over full
+    // and the recorded length is correct.
+    #[cfg(test)]
+    pub(crate) fn is_valid(&self) -> bool {
+        let mut len = 0;
+        for (i, chunk) in self.chunks.iter().enumerate() {
+            if chunk.is_empty() || (i > 0 && self.chunks[i - 1].last() >= chunk.first()) {
+                return false;
+            }
+            if chunk.len() > 2 * self.chunk_len
+                || (self.chunks.len() > 1 && chunk.len() < self.chunk_len / 2)
+            {
+                return false;
+            }
+            for j in 1..chunk.len() {
+                if chunk[j - 1] >= chunk[j] {
+                    return false;
+                }
+            }
+            len += chunk.len();
+        }
+        len == self.len
+    }
+
+    /// Return the number of items in this set.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.chunks.clear();
+        self.len = 0;
+        self.chunk_len = preferred_chunk_len(0);
+    }
+
+    // Split the chunk at `index` in two if it has grown too long.
+    fn split_chunk_if_full(&mut self, index: usize) {
+        let chunk = &mut self.chunks[index];
+        if chunk.len() > 2 * self.chunk_len {
+            let tail = chunk.split_off(chunk.len() / 2);
+            self.chunks.insert(index + 1, tail);
+        }
+    }
+
+    // Merge the chunk at `index` with a neighbour if it has become too short.
+    fn merge_chunk_if_short(&mut self, index: usize) {
+        if self.chunks[index].is_empty() {
+            self.chunks.remove(index);
+        } else if self.chunks.len() > 1 && self.chunks[index].len() < self.chunk_len / 2 {
+            let index = if index + 1 < self.chunks.len() {
+                index
+            } else {
+                index - 1
+            };
+            let mut next = self.chunks.remove(index + 1);
+            self.chunks[index].append(&mut next);
+            self.split_chunk_if_full(index);
+        }
+    }
+
+    // Redistribute the members between chunks of the preferred length if the length of
+    // the set has changed enough since the chunk length was last chosen.
+    fn rechunk_if_needed(&mut self) {
+        if needs_rechunk(self.chunk_len, self.len) {
+            self.chunk_len = preferred_chunk_len(self.len);
+            let members: Vec<T> = self.chunks.drain(..).flatten().collect();
+            self.chunks = split_into_chunks(members, self.chunk_len);
+        }
+    }
+
+    // Return the index of the chunk that would contain `item` if it were present.
+    fn chunk_index<Q>(&self, item: &Q) -> Option<usize>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        if self.chunks.is_empty() {
+            None
+        } else {
+            let index = self
+                .chunks
+                .partition_point(|chunk| chunk[chunk.len() - 1].borrow() < item);
+            Some(index.min(self.chunks.len() - 1))
+        }
+    }
+
+    // Return the (chunk, offset) position of the first item that is greater than (or equal
+    // to if `inclusive` is true) `item`.
+    fn position<Q>(&self, item: &Q, inclusive: bool) -> (usize, usize)
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let before = |t: &T| {
+            if inclusive {
+                t.borrow() < item
+            } else {
+                t.borrow() <= item
+            }
+        };
+        let chunk_index = self
+            .chunks
+            .partition_point(|chunk| before(&chunk[chunk.len() - 1]));
+        if let Some(chunk) = self.chunks.get(chunk_index) {
+            (chunk_index, chunk.partition_point(|t| before(t)))
+        } else {
+            (chunk_index, 0)
+        }
+    }
+
+    /// Insert `item` in the set and return `false`
+    /// if the item was already a member otherwise true
+    pub fn insert(&mut self, item: T) -> bool {
+        let chunk_index = match self.chunk_index(&item) {
+            Some(chunk_index) => chunk_index,
+            None => {
+                self.chunks.push(vec![item]);
+                self.len = 1;
+                return true;
+            }
+        };
+        let chunk = &mut self.chunks[chunk_index];
+        if let Err(index) = chunk.binary_search(&item) {
+            chunk.insert(index, item);
+            self.len += 1;
+            self.split_chunk_if_full(chunk_index);
+            self.rechunk_if_needed();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Remove `item` from the set and return `true`
+    /// if the item was a member and false otherwise
+    pub fn remove<Q>(&mut self, item: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        if let Some(chunk_index) = self.chunk_index(item) {
+            let chunk = &mut self.chunks[chunk_index];
+            if let Ok(index) = chunk.binary_search_by_key(&item, |x| x.borrow()) {
+                chunk.remove(index);
+                self.len -= 1;
+                self.merge_chunk_if_short(chunk_index);
+                self.rechunk_if_needed();
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Return `true` if `item` is a member of the set
+    pub fn contains<Q>(&self, item: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        if let Some(chunk_index) = self.chunk_index(item) {
+            self.chunks[chunk_index]
+                .binary_search_by_key(&item, |x| x.borrow())
+                .is_ok()
+        } else {
+            false
+        }
+    }
+
+    /// Returns the `ChunkedOrderedSet`'s first element in ascending order, or `None` if it is empty.
+    pub fn first(&self) -> Option<&T> {
+        self.chunks.first().and_then(|chunk| chunk.first())
+    }
+
+    /// Returns the `ChunkedOrderedSet`'s last element in ascending order, or `None` if it is empty.
+    pub fn last(&self) -> Option<&T> {
+        self.chunks.last().and_then(|chunk| chunk.last())
+    }
+
+    /// Returns an iterator that iterates over the `ChunkedOrderedSet`'s elements in ascending
+    /// order
+    pub fn iter(&self) -> ChunkedSetIter<'_, T> {
+        let end_offset = self.chunks.last().map_or(0, |chunk| chunk.len());
+        ChunkedSetIter::new(&self.chunks, 0, end_offset)
+    }
+
+    /// Returns an iterator that iterates over the `ChunkedOrderedSet`'s elements that fall within
+    /// the given range in ascending order
+    pub fn range<Q, R>(&self, range: R) -> ChunkedSetIter<'_, T>
+    where
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+        T: Borrow<Q>,
+    {
+        crate::check_range_bounds(&range);
+        let (start_chunk, start_offset) = match range.start_bound() {
+            Bound::Unbounded => (0, 0),
+            Bound::Included(item) => self.position(item, true),
+            Bound::Excluded(item) => self.position(item, false),
+        };
+        let (end_chunk, end_offset) = match range.end_bound() {
+            Bound::Unbounded => (self.chunks.len(), 0),
+            Bound::Included(item) => self.position(item, false),
+            Bound::Excluded(item) => self.position(item, true),
+        };
+        if (start_chunk, start_offset) >= (end_chunk, end_offset) {
+            ChunkedSetIter::new(&[], 0, 0)
+        } else if end_offset == 0 {
+            let chunks = &self.chunks[start_chunk..end_chunk];
+            let end_offset = chunks.last().map_or(0, |chunk| chunk.len());
+            ChunkedSetIter::new(chunks, start_offset, end_offset)
+        } else {
+            ChunkedSetIter::new(
+                &self.chunks[start_chunk..=end_chunk],
+                start_offset,
+                end_offset,
+            )
+        }
+    }
+
+    /// Return true if this ChunkedOrderedSet is disjoint from the other ChunkedOrderedSet
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        are_disjoint(self.iter(), other.iter())
+    }
+
+    /// Return true if self is a subset of other
+    pub fn is_subset(&self, other: &Self) -> bool {
+        a_superset_b(other.iter(), self.iter())
+    }
+
+    /// Return true if self is a superset of other
+    pub fn is_superset(&self, other: &Self) -> bool {
+        a_superset_b(self.iter(), other.iter())
+    }
+}
+
+// Equality, hashing and formatting depend only on the contents of the set and not on
+// how those contents happen to be distributed between chunks.
+impl<T: Ord> PartialEq for ChunkedOrderedSet<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Ord> Eq for ChunkedOrderedSet<T> {}
+
+impl<T: Ord + Hash> Hash for ChunkedOrderedSet<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len);
+        for item in self.iter() {
+            item.hash(state);
+        }
+    }
+}
+
+impl<T: Ord + fmt::Debug> fmt::Debug for ChunkedOrderedSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+// Split the sorted `members` into chunks of (at most) `chunk_len` items.
+fn split_into_chunks<T>(members: Vec<T>, chunk_len: usize) -> Vec<Vec<T>> {
+    let mut members = members.into_iter();
+    chunk_sizes(members.len(), chunk_len)
+        .map(|size| members.by_ref().take(size).collect())
+        .collect()
+}
+
+impl<T: Ord> From<OrderedSet<T>> for ChunkedOrderedSet<T> {
+    fn from(set: OrderedSet<T>) -> Self {
+        let len = set.members.len();
+        let chunk_len = preferred_chunk_len(len);
+        let chunks = split_into_chunks(set.members, chunk_len);
+        Self {
+            chunks,
+            len,
+            chunk_len,
+        }
+    }
+}
+
+impl<T: Ord> From<ChunkedOrderedSet<T>> for OrderedSet<T> {
+    fn from(set: ChunkedOrderedSet<T>) -> Self {
+        Self::from_storage(set.chunks.into_iter().flatten().collect())
+    }
+}
+
+impl<T: Ord> FromIterator<T> for ChunkedOrderedSet<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        OrderedSet::from_iter(iter).into()
+    }
+}
+
+impl<'a, T: Ord> IntoIterator for &'a ChunkedOrderedSet<T> {
+    type Item = &'a T;
+    type IntoIter = ChunkedSetIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: Ord> IntoIterator for ChunkedOrderedSet<T> {
+    type Item = T;
+    type IntoIter = core::iter::Flatten<alloc::vec::IntoIter<Vec<T>>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.chunks.into_iter().flatten()
+    }
+}
+
+// CHUNKED SET ITERATOR
+
+/// An Iterator over the elements in a `ChunkedOrderedSet`
+pub struct ChunkedSetIter<'a, T: Ord> {
+    chunks: &'a [Vec<T>],
+    start_offset: usize,
+    end_offset: usize,
+    chunk_index: usize,
+    iter: SetIter<'a, T>,
+}
+
+impl<'a, T: Ord> ChunkedSetIter<'a, T> {
+    fn new(chunks: &'a [Vec<T>], start_offset: usize, end_offset: usize) -> Self {
+        let mut iter = Self {
+            chunks,
+            start_offset,
+            end_offset,
+            chunk_index: 0,
+            iter: SetIter::new(&[]),
+        };
+        iter.iter = iter.chunk_iter(0);
+        iter
+    }
+
+    // Return a `SetIter` over the part of the indexed chunk that is within range.
+    fn chunk_iter(&self, chunk_index: usize) -> SetIter<'a, T> {
+        let chunks = self.chunks;
+        if let Some(chunk) = chunks.get(chunk_index) {
+            let start = if chunk_index == 0 {
+                self.start_offset
+            } else {
+                0
+            };
+            let end = if chunk_index + 1 == chunks.len() {
+                self.end_offset
+            } else {
+                chunk.len()
+            };
+            SetIter::new(&chunk[start..end])
+        } else {
+            SetIter::new(&[])
+        }
+    }
+
+    // Move to the first chunk (at or after the current chunk) whose last item is not
+    // before the target.
+    fn skip_chunks<F: Fn(&T) -> bool>(&mut self, before: F) {
+        let skip = self.chunks[self.chunk_index.min(self.chunks.len())..]
+            .partition_point(|chunk| before(&chunk[chunk.len() - 1]));
+        if skip > 0 {
+            self.chunk_index += skip;
+            self.iter = self.chunk_iter(self.chunk_index);
+        }
+    }
+}
+
+impl<'a, T: Ord> Iterator for ChunkedSetIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.peek()?;
+        self.iter.next()
+    }
+}
+
+impl<'a, T: 'a + Ord> SkipAheadIterator<T> for ChunkedSetIter<'a, T> {
+    fn advance_past<Q>(&mut self, t: &Q) -> &mut Self
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.skip_chunks(|item| item.borrow() <= t);
+        self.iter.advance_past(t);
+        self
+    }
+
+    fn advance_until<Q>(&mut self, t: &Q) -> &mut Self
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.skip_chunks(|item| item.borrow() < t);
+        self.iter.advance_until(t);
+        self
+    }
+
+    fn peek(&mut self) -> Option<&T> {
+        loop {
+            if self.iter.peek().is_some() {
+                return self.iter.peek();
+            } else if self.chunk_index + 1 < self.chunks.len() {
+                self.chunk_index += 1;
+                self.iter = self.chunk_iter(self.chunk_index);
+            } else {
+                return None;
+            }
+        }
+    }
+}
+
+impl<'a, T: Ord + Clone> ToList<'a, T> for ChunkedSetIter<'a, T> {}
+
+impl<'a, T: Ord + Clone> ToSet<'a, T> for ChunkedSetIter<'a, T> {}
+
+impl<'a, T: Ord + Clone> IterSetOperations<T> for ChunkedSetIter<'a, T> {}
+
+impl<'a, T: Ord + Clone> Selection<'a, T> for ChunkedSetIter<'a, T> {}
+
+macro_rules! impl_op_for_chunked_set_iter {
+    ( $op:ident, $op_fn:ident, $output:ident, [ $( $item:tt )* ], $doc:meta ) => {
+        impl<'a, T, I> $op<I> for ChunkedSetIter<'a, T>
+        where
+            T: Ord,
+            I: SkipAheadIterator<T, $( $item )*>,
+        {
+            type Output = $output<T, Self, I>;
+
+            #[$doc]
+            fn $op_fn(self, other: I) -> Self::Output {
+                $output::new(self, other)
+            }
+        }
+    };
+}
+
+impl_op_for_chunked_set_iter!(
+    BitOr,
+    bitor,
+    Union,
+    [Item = &'a T],
+    doc = "Return a new ordered iterator over the set union of the contents
+    of this iterator and other."
+);
+impl_op_for_chunked_set_iter!(
+    BitAnd,
+    bitand,
+    Intersection,
+    [],
+    doc = "Return a new ordered iterator over the set intersection of the contents
+    of this iterator and other."
+);
+impl_op_for_chunked_set_iter!(
+    BitXor,
+    bitxor,
+    SymmetricDifference,
+    [Item = &'a T],
+    doc = "Return a new ordered iterator over the symmetric set difference
+    between the contents of this iterator and other."
+);
+impl_op_for_chunked_set_iter!(
+    Sub,
+    sub,
+    Difference,
+    [],
+    doc = "Return a new ordered iterator over the set difference of the contents
+    of this iterator and other."
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::random_sequence_below;
+
+    #[test]
+    fn chunked_set_insert_and_remove() {
+        let mut set = ChunkedOrderedSet::<u64>::new();
+        let mut ref_set = OrderedSet::<u64>::new();
+        for item in random_sequence_below(5000, 100_000) {
+            assert_eq!(set.insert(item), ref_set.insert(item));
+            assert!(set.contains(&item));
+        }
+        assert!(set.is_valid());
+        assert!(set.chunks.len() > 1);
+        assert_eq!(set.len(), ref_set.len());
+        assert_eq!(set.iter().to_list(), ref_set.iter().to_list());
+        for item in random_sequence_below(2500, 100_000) {
+            assert_eq!(set.remove(&item), ref_set.remove(&item));
+            assert!(!set.contains(&item));
+        }
+        assert!(set.is_valid());
+        assert_eq!(set.first(), ref_set.first());
+        assert_eq!(set.last(), ref_set.last());
+        assert_eq!(OrderedSet::from(set), ref_set);
+    }
+
+    #[test]
+    fn chunked_set_chunk_len_follows_square_root() {
+        assert_eq!(preferred_chunk_len(0), MIN_CHUNK_LEN);
+        assert_eq!(preferred_chunk_len(10_000), 100);
+        assert_eq!(preferred_chunk_len(10_200), 100);
+        let mut set = ChunkedOrderedSet::<u64>::new();
+        for item in 0..100_000 {
+            set.insert(item);
+            if item % 1000 == 0 {
+                assert!(set.is_valid());
+            }
+        }
+        assert!(set.is_valid());
+        assert!(!needs_rechunk(set.chunk_len, 100_000));
+        assert!(set.chunks.len() <= 2 * preferred_chunk_len(100_000));
+        for item in 0..99_000 {
+            set.remove(&item);
+            if item % 1000 == 0 {
+                assert!(set.is_valid());
+            }
+        }
+        assert!(set.is_valid());
+        assert!(!needs_rechunk(set.chunk_len, 1000));
+        assert_eq!(set.iter().to_list(), (99_000..100_000).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn chunked_set_equality_ignores_chunk_layout() {
+        use std::collections::hash_map::DefaultHasher;
+
+        let collected: ChunkedOrderedSet<u64> = (0..600).collect();
+        let mut inserted = ChunkedOrderedSet::<u64>::new();
+        for item in 0..600 {
+            inserted.insert(item);
+        }
+        assert_ne!(collected.chunks.len(), inserted.chunks.len());
+        assert_eq!(collected, inserted);
+        let hash = |set: &ChunkedOrderedSet<u64>| {
+            let mut hasher = DefaultHasher::new();
+            set.hash(&mut hasher);
+            hasher.finish()
+        };
+        assert_eq!(hash(&collected), hash(&inserted));
+        assert_eq!(format!("{:?}", collected), format!("{:?}", inserted));
+        inserted.remove(&300);
+        assert_ne!(collected, inserted);
+    }
+
+    #[test]
+    fn chunked_set_range() {
+        let set: ChunkedOrderedSet<u64> = (0..5000).map(|x| x * 2).collect();
+        assert!(set.is_valid());
+        let ref_set: OrderedSet<u64> = (0..5000).map(|x| x * 2).collect();
+        for (start, end) in &[(0, 1), (511, 513), (250, 1000), (1023, 1025), (1, 9999)] {
+            assert_eq!(
+                set.range(start..end).to_list(),
+                ref_set.range(start..end).to_list()
+            );
+            assert_eq!(
+                set.range(start..=end).to_list(),
+                ref_set.range(start..=end).to_list()
+            );
+        }
+        assert_eq!(set.range(..).count(), 5000);
+        assert_eq!(set.range(200..200).count(), 0);
+        assert_eq!(set.range(20000..).count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "range start is greater than range end")]
+    fn chunked_set_inverted_range() {
+        let set: ChunkedOrderedSet<u64> = (0..100).collect();
+        set.range((Bound::Included(50), Bound::Excluded(40)));
+    }
+
+    #[test]
+    fn chunked_set_iter_operations() {
+        let set1: ChunkedOrderedSet<u64> = (0..3000).map(|x| x * 2).collect();
+        let set2: ChunkedOrderedSet<u64> = (0..2000).map(|x| x * 3).collect();
+        let ref_set1: OrderedSet<u64> = (0..3000).map(|x| x * 2).collect();
+        let ref_set2: OrderedSet<u64> = (0..2000).map(|x| x * 3).collect();
+        assert_eq!((set1.iter() & set2.iter()).to_set(), &ref_set1 & &ref_set2);
+        assert_eq!(
+            (set1.iter() | ref_set2.iter()).to_set(),
+            &ref_set1 | &ref_set2
+        );
+        assert_eq!((set1.iter() - set2.iter()).to_set(), &ref_set1 - &ref_set2);
+        assert_eq!((set1.iter() ^ set2.iter()).to_set(), &ref_set1 ^ &ref_set2);
+        let mut iter = set1.iter();
+        assert_eq!(iter.advance_until(&3001).next(), Some(&3002));
+        assert_eq!(iter.advance_past(&4000).next(), Some(&4002));
+        assert!(!set1.is_disjoint(&set2));
+        let evens: ChunkedOrderedSet<u64> = (0..1000).map(|x| x * 6).collect();
+        assert!(evens.is_subset(&set1));
+        assert!(set2.is_superset(&evens));
+    }
+}
@@ -0,0 +1,325 @@
+//! Errors reported by the ordered collections.
+
+use core::fmt;
+
+#[cfg(feature = "alloc")]
+pub use alloc::collections::TryReserveError;
+
+#[cfg(feature = "alloc")]
+use crate::ordered_map::OccupiedError;
+use crate::storage::CapacityError;
+
+/// The crate's error type which any of the errors returned by the collections' fallible
+/// methods can be converted into (e.g. by the `?` operator).
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The items given to a constructor were out of order or contained duplicates.
+    Order(OrderError),
+    /// The keys and values given to a map constructor differed in number.
+    Length(LengthError),
+    /// A fixed capacity collection was full.
+    Capacity,
+    /// Memory for the collection could not be allocated.
+    #[cfg(feature = "alloc")]
+    TryReserve(TryReserveError),
+    /// The key being inserted was already in the map.
+    Occupied,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Order(error) => error.fmt(f),
+            Error::Length(error) => error.fmt(f),
+            Error::Capacity => write!(f, "insufficient capacity"),
+            #[cfg(feature = "alloc")]
+            Error::TryReserve(error) => error.fmt(f),
+            Error::Occupied => write!(f, "key already exists"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+impl<T> From<OrderError<T>> for Error {
+    fn from(error: OrderError<T>) -> Self {
+        Error::Order(error.simplify())
+    }
+}
+
+impl<T> From<LengthError<T>> for Error {
+    fn from(error: LengthError<T>) -> Self {
+        Error::Length(error.simplify())
+    }
+}
+
+impl<T> From<PartsError<T>> for Error {
+    fn from(error: PartsError<T>) -> Self {
+        match error {
+            PartsError::Order(error) => error.into(),
+            PartsError::Length(error) => error.into(),
+        }
+    }
+}
+
+impl<T> From<CapacityError<T>> for Error {
+    fn from(_: CapacityError<T>) -> Self {
+        Error::Capacity
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<TryReserveError> for Error {
+    fn from(error: TryReserveError) -> Self {
+        Error::TryReserve(error)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K: Ord, V> From<OccupiedError<'_, K, V>> for Error {
+    fn from(_: OccupiedError<'_, K, V>) -> Self {
+        Error::Occupied
+    }
+}
+
+/// The error returned when the items given to a constructor that requires sorted
+/// input are out of order or contain duplicates. Constructors that take ownership of
+/// their input hand it back in the error so that it can be used in some other way
+/// (e.g. sorted) without having been copied.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OrderError<T = ()> {
+    index: usize,
+    duplicate: bool,
+    input: T,
+}
+
+impl OrderError {
+    // Check that `items` are in strictly ascending order.
+    pub(crate) fn check<T: Ord>(items: &[T]) -> Result<(), Self> {
+        Self::check_by_key(items, |item| item)
+    }
+
+    // Check that the keys extracted from `items` by `key` are in strictly ascending order.
+    pub(crate) fn check_by_key<T, K, F>(items: &[T], key: F) -> Result<(), Self>
+    where
+        K: Ord + ?Sized,
+        F: Fn(&T) -> &K,
+    {
+        match items
+            .windows(2)
+            .position(|pair| key(&pair[0]) >= key(&pair[1]))
+        {
+            Some(index) => Err(Self {
+                index: index + 1,
+                duplicate: key(&items[index]) == key(&items[index + 1]),
+                input: (),
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<T> OrderError<T> {
+    /// Return the index of the first item that is not greater than the item before it.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Return `true` if the offending item is equal to the item before it rather
+    /// than less than it.
+    pub fn is_duplicate(&self) -> bool {
+        self.duplicate
+    }
+
+    /// Return the input that was given to the constructor.
+    pub fn into_input(self) -> T {
+        self.input
+    }
+
+    /// Discard the rejected input.
+    pub fn simplify(self) -> OrderError {
+        self.with_input(())
+    }
+
+    // Replace the input carried by the error with `input`.
+    pub(crate) fn with_input<U>(self, input: U) -> OrderError<U> {
+        OrderError {
+            index: self.index,
+            duplicate: self.duplicate,
+            input,
+        }
+    }
+}
+
+impl<T> fmt::Debug for OrderError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("OrderError")
+            .field("index", &self.index)
+            .field("duplicate", &self.duplicate)
+            .finish()
+    }
+}
+
+impl<T> fmt::Display for OrderError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.duplicate {
+            write!(f, "item at index {} is a duplicate", self.index)
+        } else {
+            write!(f, "item at index {} is out of order", self.index)
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> std::error::Error for OrderError<T> {}
+
+/// The error returned when the keys and values given to a map constructor that takes
+/// them separately differ in number. As for `OrderError` the constructor's input is
+/// handed back.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LengthError<T = ()> {
+    keys: usize,
+    values: usize,
+    input: T,
+}
+
+impl LengthError {
+    // Check that there are as many values as keys.
+    pub(crate) fn check(keys: usize, values: usize) -> Result<(), Self> {
+        if keys == values {
+            Ok(())
+        } else {
+            Err(Self {
+                keys,
+                values,
+                input: (),
+            })
+        }
+    }
+}
+
+impl<T> LengthError<T> {
+    /// Return the number of keys given to the constructor.
+    pub fn keys_len(&self) -> usize {
+        self.keys
+    }
+
+    /// Return the number of values given to the constructor.
+    pub fn values_len(&self) -> usize {
+        self.values
+    }
+
+    /// Return the input that was given to the constructor.
+    pub fn into_input(self) -> T {
+        self.input
+    }
+
+    /// Discard the rejected input.
+    pub fn simplify(self) -> LengthError {
+        self.with_input(())
+    }
+
+    // Replace the input carried by the error with `input`.
+    pub(crate) fn with_input<U>(self, input: U) -> LengthError<U> {
+        LengthError {
+            keys: self.keys,
+            values: self.values,
+            input,
+        }
+    }
+}
+
+impl<T> fmt::Debug for LengthError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("LengthError")
+            .field("keys", &self.keys)
+            .field("values", &self.values)
+            .finish()
+    }
+}
+
+impl<T> fmt::Display for LengthError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} keys but {} values", self.keys, self.values)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> std::error::Error for LengthError<T> {}
+
+/// The error returned by the map constructors that take keys and values separately.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PartsError<T = ()> {
+    /// The keys were out of order or contained duplicates.
+    Order(OrderError<T>),
+    /// The keys and values differed in number.
+    Length(LengthError<T>),
+}
+
+impl PartsError {
+    // Check that there are as many values as keys and that the keys are in strictly
+    // ascending order.
+    pub(crate) fn check<K: Ord, V>(keys: &[K], values: &[V]) -> Result<(), Self> {
+        LengthError::check(keys.len(), values.len())?;
+        OrderError::check(keys)?;
+        Ok(())
+    }
+}
+
+impl<T> PartsError<T> {
+    /// Return the input that was given to the constructor.
+    pub fn into_input(self) -> T {
+        match self {
+            PartsError::Order(error) => error.into_input(),
+            PartsError::Length(error) => error.into_input(),
+        }
+    }
+
+    /// Discard the rejected input.
+    pub fn simplify(self) -> PartsError {
+        self.with_input(())
+    }
+
+    // Replace the input carried by the error with `input`.
+    pub(crate) fn with_input<U>(self, input: U) -> PartsError<U> {
+        match self {
+            PartsError::Order(error) => PartsError::Order(error.with_input(input)),
+            PartsError::Length(error) => PartsError::Length(error.with_input(input)),
+        }
+    }
+}
+
+impl<T> From<OrderError<T>> for PartsError<T> {
+    fn from(error: OrderError<T>) -> Self {
+        PartsError::Order(error)
+    }
+}
+
+impl<T> From<LengthError<T>> for PartsError<T> {
+    fn from(error: LengthError<T>) -> Self {
+        PartsError::Length(error)
+    }
+}
+
+impl<T> fmt::Debug for PartsError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PartsError::Order(error) => f.debug_tuple("Order").field(error).finish(),
+            PartsError::Length(error) => f.debug_tuple("Length").field(error).finish(),
+        }
+    }
+}
+
+impl<T> fmt::Display for PartsError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PartsError::Order(error) => error.fmt(f),
+            PartsError::Length(error) => error.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> std::error::Error for PartsError<T> {}
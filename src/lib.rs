@@ -1,6 +1,21 @@
 //! Sets and Maps that iterate over their contents in a defined order.
+//!
+//! The crate is `no_std` when the default `std` feature is disabled. The growable collections
+//! need the `alloc` feature while the fixed capacity `ArrayOrderedSet` and `ArrayOrderedMap`
+//! are always available.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+// The tests use the standard library even when the crate itself is built without it.
+#[cfg(all(test, not(feature = "std")))]
+#[macro_use]
+extern crate std;
 
 /// Creates an `OrderedSet`  containing the arguments.
+#[cfg(feature = "alloc")]
 #[macro_export]
 macro_rules! ordered_set {
     ( $( $x:expr ),* ) => {
@@ -11,6 +26,7 @@ macro_rules! ordered_set {
 }
 
 /// Creates an `OrderedMap` containing the arguments interpreted as key-value pairs.
+#[cfg(feature = "alloc")]
 #[macro_export]
 macro_rules! ordered_map {
     ( $( ($k:expr, $v:expr) ),* ) => {
@@ -24,15 +40,34 @@ macro_rules! ordered_map {
     }
 }
 
+pub mod adapters;
+pub mod array_map;
+pub mod array_set;
+#[cfg(feature = "alloc")]
+pub mod chunked_map;
+#[cfg(feature = "alloc")]
+pub mod chunked_set;
+pub mod error;
+pub mod map_ref;
 pub mod ordered_map;
 pub mod ordered_set;
+#[cfg(feature = "rayon")]
+pub mod parallel;
+#[cfg(feature = "alloc")]
+pub mod persistent_map;
+pub mod prefix;
+pub mod set_ref;
+pub mod static_set;
+pub mod storage;
+#[cfg(test)]
+mod test_helpers;
 
-fn lower_bound_index<T, K>(members: &[T], bound: std::ops::Bound<&K>) -> usize
+fn lower_bound_index<T, K>(members: &[T], bound: core::ops::Bound<&K>) -> usize
 where
-    K: Ord + Sized,
-    T: Ord + std::borrow::Borrow<K>,
+    K: Ord + ?Sized,
+    T: Ord + core::borrow::Borrow<K>,
 {
-    use std::ops::Bound::*;
+    use core::ops::Bound::*;
     match bound {
         Unbounded => 0,
         Included(item) => match members.binary_search_by_key(&item, |x| x.borrow()) {
@@ -46,12 +81,12 @@ where
     }
 }
 
-fn upper_bound_index<T, K>(members: &[T], bound: std::ops::Bound<&K>) -> usize
+fn upper_bound_index<T, K>(members: &[T], bound: core::ops::Bound<&K>) -> usize
 where
-    K: Ord + Sized,
-    T: Ord + std::borrow::Borrow<K>,
+    K: Ord + ?Sized,
+    T: Ord + core::borrow::Borrow<K>,
 {
-    use std::ops::Bound::*;
+    use core::ops::Bound::*;
     match bound {
         Unbounded => members.len(),
         Included(item) => match members.binary_search_by_key(&item, |x| x.borrow()) {
@@ -65,24 +100,71 @@ where
     }
 }
 
+// Panic, with the same messages as `BTreeMap::range`, if `range` has its start after its
+// end or has equal bounds that are both excluded.
+fn check_range_bounds<K, R>(range: &R)
+where
+    K: Ord + ?Sized,
+    R: core::ops::RangeBounds<K>,
+{
+    use core::ops::Bound::*;
+    match (range.start_bound(), range.end_bound()) {
+        (Excluded(start), Excluded(end)) if start == end => {
+            panic!("range start and end are equal and excluded")
+        }
+        (Included(start), Included(end))
+        | (Included(start), Excluded(end))
+        | (Excluded(start), Included(end))
+        | (Excluded(start), Excluded(end))
+            if start > end =>
+        {
+            panic!("range start is greater than range end")
+        }
+        _ => {}
+    }
+}
+
 fn range_indices<T, K, R>(members: &[T], range: R) -> (usize, usize)
 where
-    K: Ord + Sized,
-    R: std::ops::RangeBounds<K>,
-    T: Ord + std::borrow::Borrow<K>,
+    K: Ord + ?Sized,
+    R: core::ops::RangeBounds<K>,
+    T: Ord + core::borrow::Borrow<K>,
 {
+    check_range_bounds(&range);
     let start_index = lower_bound_index(members, range.start_bound());
     let end_index = upper_bound_index(members, range.end_bound());
     (start_index, end_index)
 }
 
+#[cfg(feature = "alloc")]
+pub use adapters::{BTreeMapRange, BTreeSetRange};
+pub use adapters::{Checked, Sorted};
+pub use array_map::ArrayOrderedMap;
+pub use array_set::ArrayOrderedSet;
+#[cfg(feature = "alloc")]
+pub use chunked_map::ChunkedOrderedMap;
+#[cfg(feature = "alloc")]
+pub use chunked_set::ChunkedOrderedSet;
+pub use error::{Error, LengthError, OrderError, PartsError};
+pub use map_ref::OrderedMapRef;
+#[cfg(feature = "alloc")]
 pub use ordered_map::OrderedMap;
+#[cfg(feature = "alloc")]
 pub use ordered_set::OrderedSet;
+#[cfg(feature = "alloc")]
+pub use persistent_map::PersistentOrderedMap;
+pub use prefix::{FirstField, Prefix};
+pub use set_ref::OrderedSetRef;
+pub use static_set::StaticOrderedSet;
+pub use storage::CapacityError;
+#[cfg(feature = "alloc")]
+pub use storage::{Heap, Inline, InlineStore, Storage, StorageKind};
 
-#[cfg(test)]
+#[cfg(all(test, feature = "alloc"))]
 mod tests {
     use super::*;
     use crate::ordered_set::ord_set_iterators::ToList;
+    use crate::test_helpers::prelude::*;
 
     #[test]
     fn ordered_set_macro() {
@@ -0,0 +1,245 @@
+//! Read only views of maps held in borrowed slices of keys and values.
+//! They give data that is already sorted access to the map iterators without copying.
+
+use core::borrow::Borrow;
+use core::fmt;
+use core::ops::Index;
+
+use crate::error::PartsError;
+use crate::ordered_map::ord_map_iterators::{MapIter, ValueIter};
+use crate::ordered_set::ord_set_iterators::SetIter;
+use crate::set_ref::OrderedSetRef;
+#[cfg(feature = "alloc")]
+use crate::OrderedMap;
+
+/// A read only view of a map held in a borrowed slice of keys ordered according to
+/// Ord (with no duplicates) and a borrowed slice of the corresponding values.
+#[derive(PartialEq, Eq, Hash)]
+pub struct OrderedMapRef<'a, K: Ord, V> {
+    keys: &'a [K],
+    values: &'a [V],
+}
+
+impl<'a, K: Ord, V> OrderedMapRef<'a, K, V> {
+    /// Create an `OrderedMapRef` viewing `keys` and `values` or return a `PartsError`
+    /// reporting that `keys` and `values` have different lengths or locating the first
+    /// key that is out of order or a duplicate.
+    pub fn new(keys: &'a [K], values: &'a [V]) -> Result<Self, PartsError> {
+        PartsError::check(keys, values)?;
+        Ok(Self { keys, values })
+    }
+
+    /// Create an `OrderedMapRef` viewing `keys` and `values` where `keys` must already be
+    /// sorted and free of duplicates. This is not checked and the map's methods will give
+    /// incorrect results if it is not the case.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `keys` and `values` have different lengths.
+    pub fn from_sorted_unchecked(keys: &'a [K], values: &'a [V]) -> Self {
+        assert_eq!(keys.len(), values.len(), "keys and values lengths differ");
+        Self { keys, values }
+    }
+
+    /// Return the number of items in this map.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Returns `true` if there is an entry for `key` in the map and `false` otherwise.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.keys.binary_search_by_key(&key, |x| x.borrow()).is_ok()
+    }
+
+    /// Returns a reference to the value in the map associated with `key` if
+    /// it exists and `None` otherwise.
+    pub fn get<Q>(&self, key: &Q) -> Option<&'a V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let index = self.keys.binary_search_by_key(&key, |x| x.borrow()).ok()?;
+        Some(&self.values[index])
+    }
+
+    /// Returns the key-value pair in the map associated with `key` if it exists and
+    /// `None` otherwise.
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&'a K, &'a V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let index = self.keys.binary_search_by_key(&key, |x| x.borrow()).ok()?;
+        Some((&self.keys[index], &self.values[index]))
+    }
+
+    /// Returns an iterator visiting all key-value pairs in ascending order of their keys.
+    /// The iterator item type is `(&'a K, &'a V)`.
+    pub fn iter(&self) -> MapIter<'a, K, V> {
+        MapIter::new(self.keys, self.values)
+    }
+
+    /// Returns an iterator visiting all key-value pairs whose key falls within the specified
+    /// range in ascending order of their keys.
+    /// The iterator item type is `(&'a K, &'a V)`.
+    pub fn range<Q, R>(&self, range: R) -> MapIter<'a, K, V>
+    where
+        Q: Ord + ?Sized,
+        R: core::ops::RangeBounds<Q>,
+        K: Borrow<Q>,
+    {
+        let (start_index, end_index) = super::range_indices(self.keys, range);
+        MapIter::new(
+            &self.keys[start_index..end_index],
+            &self.values[start_index..end_index],
+        )
+    }
+
+    /// Returns a `SetIter` iterator visiting all keys in the map in ascending order.
+    pub fn keys(&self) -> SetIter<'a, K> {
+        SetIter::new(self.keys)
+    }
+
+    /// Returns a read only view of the map's keys as a set.
+    pub fn key_set(&self) -> OrderedSetRef<'a, K> {
+        OrderedSetRef::from_sorted_unchecked(self.keys)
+    }
+
+    /// Returns an iterator visiting all values in the map in ascending order of their keys.
+    pub fn values(&self) -> ValueIter<'a, K, V> {
+        ValueIter::new(self.keys, self.values)
+    }
+}
+
+impl<K: Ord, V> Clone for OrderedMapRef<'_, K, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K: Ord, V> Copy for OrderedMapRef<'_, K, V> {}
+
+impl<K: Ord + fmt::Debug, V: fmt::Debug> fmt::Debug for OrderedMapRef<'_, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K, V, Q> Index<&Q> for OrderedMapRef<'_, K, V>
+where
+    K: Ord + Borrow<Q>,
+    Q: Ord + fmt::Debug + ?Sized,
+{
+    type Output = V;
+
+    /// Returns a reference to the value associated with `key`.
+    ///
+    /// # Panics
+    ///
+    /// Panics, naming `key` in the message, if `key` is not in the map.
+    fn index(&self, key: &Q) -> &Self::Output {
+        match self.get(key) {
+            Some(value) => value,
+            None => panic!("Unknown key {:?}", key),
+        }
+    }
+}
+
+impl<'a, K: Ord, V> IntoIterator for OrderedMapRef<'a, K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = MapIter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K: Ord, V> IntoIterator for &OrderedMapRef<'a, K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = MapIter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, K: Ord, V> From<&'a OrderedMap<K, V>> for OrderedMapRef<'a, K, V> {
+    fn from(map: &'a OrderedMap<K, V>) -> Self {
+        Self::from_sorted_unchecked(&map.keys, &map.values)
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::ordered_map::ord_map_iterators::{MapIterFilter, MapIterMerge, ToMap};
+    use crate::test_helpers::prelude::*;
+
+    static KEYS: &[&str] = &["aaa", "bbb", "ccc", "fff", "hhh", "iii", "qqq", "zzz"];
+    static VALUES: &[u32] = &[1, 2, 3, 6, 8, 9, 17, 26];
+
+    #[test]
+    fn map_ref_validation() {
+        assert!(OrderedMapRef::new(KEYS, VALUES).is_ok());
+        match OrderedMapRef::new(&[3, 1, 2], &[0, 0, 0]) {
+            Err(PartsError::Order(err)) => assert_eq!(err.index(), 1),
+            _ => panic!("expected an order error"),
+        }
+        match OrderedMapRef::new(KEYS, &VALUES[1..]) {
+            Err(PartsError::Length(err)) => {
+                assert_eq!((err.keys_len(), err.values_len()), (8, 7));
+                assert_eq!(err.to_string(), "8 keys but 7 values");
+            }
+            _ => panic!("expected a length error"),
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn map_ref_unchecked_length_mismatch() {
+        let _ = OrderedMapRef::from_sorted_unchecked(KEYS, &VALUES[1..]);
+    }
+
+    #[test]
+    fn map_ref_read_api() {
+        let map = OrderedMapRef::new(KEYS, VALUES).unwrap();
+        assert_eq!(map.len(), 8);
+        assert!(map.contains_key("qqq"));
+        assert_eq!(map.get("hhh"), Some(&8));
+        assert_eq!(map.get("ggg"), None);
+        assert_eq!(map[&"zzz"], 26);
+        assert_eq!(map.get_key_value("aaa"), Some((&"aaa", &1)));
+        let keys: Vec<&str> = map.range("b".."g").map(|(key, _)| *key).collect();
+        assert_eq!(keys, vec!["bbb", "ccc", "fff"]);
+        let values: Vec<u32> = map.values().cloned().collect();
+        assert_eq!(values, VALUES.to_vec());
+        let other = OrderedMapRef::new(&KEYS[2..5], &[30, 60, 80]).unwrap();
+        let keys: Vec<&str> = map
+            .iter()
+            .except(other.keys())
+            .map(|(key, _)| *key)
+            .collect();
+        assert_eq!(keys, vec!["aaa", "bbb", "iii", "qqq", "zzz"]);
+        let merged = (other.iter() | map.iter().except(other.keys())).to_map();
+        assert_eq!(merged.get("fff"), Some(&60));
+        assert_eq!(merged.len(), 8);
+        let view = OrderedMapRef::from(&merged);
+        assert_eq!(view.key_set(), map.key_set());
+        assert_eq!(
+            view.iter()
+                .only(other.keys())
+                .merge(map.range(.."c"))
+                .count(),
+            5
+        );
+    }
+}
@@ -1,30 +1,65 @@
-use std::borrow::Borrow;
-use std::convert::From;
-use std::default::Default;
-use std::iter::FromIterator;
-use std::ops::{Index, IndexMut};
-use std::vec;
+#[cfg(feature = "alloc")]
+use core::borrow::Borrow;
+#[cfg(feature = "alloc")]
+use core::convert::From;
+#[cfg(feature = "alloc")]
+use core::default::Default;
+#[cfg(feature = "alloc")]
+use core::fmt;
+#[cfg(feature = "alloc")]
+use core::iter::FromIterator;
+#[cfg(feature = "alloc")]
+use core::ops::{BitAnd, BitAndAssign, Index, IndexMut, Sub, SubAssign};
 
+#[cfg(feature = "alloc")]
+use alloc::vec;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "alloc")]
+mod map_combine;
+#[cfg(feature = "alloc")]
+pub mod map_diff;
+#[cfg(feature = "alloc")]
 pub mod map_entry;
 pub mod ord_map_iterators;
 
+#[cfg(feature = "alloc")]
+pub use self::map_diff::*;
+#[cfg(feature = "alloc")]
 pub use self::map_entry::*;
 
+#[cfg(feature = "alloc")]
+pub use self::ord_map_iterators::{MapDrain, ToMap};
 pub use self::ord_map_iterators::{
-    MapDrain, MapIter, MapIterFilter, MapIterMerge, MapIterMut, MapIterMutFilter, MapIterMutMerge,
-    MapMergeIter, MapMergeIterMut, ToMap, ValueIter, ValueIterMut,
+    MapIter, MapIterFilter, MapIterMerge, MapIterMut, MapIterMutFilter, MapIterMutMerge,
+    MapMergeIter, MapMergeIterMut, ValueIter, ValueIterMut,
 };
 
 pub use crate::ordered_set::ord_set_iterators::{
     Difference, Intersection, SetIter, SymmetricDifference, Union,
 };
 
+#[cfg(feature = "alloc")]
+use crate::error::{OrderError, PartsError, TryReserveError};
+#[cfg(feature = "alloc")]
+use crate::prefix::{prefix_indices, FirstField, Prefix};
+#[cfg(feature = "alloc")]
+use crate::OrderedSet;
+
+#[cfg(feature = "alloc")]
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
 pub struct OrderedMap<K: Ord, V> {
     pub(crate) keys: Vec<K>,
     pub(crate) values: Vec<V>,
 }
 
+/// The error returned by `OrderedMap::from_sorted_parts()` which hands back the keys
+/// and values that it was given.
+#[cfg(feature = "alloc")]
+pub type MapPartsError<K, V> = PartsError<(Vec<K>, Vec<V>)>;
+
+#[cfg(feature = "alloc")]
 impl<K: Ord, V> Default for OrderedMap<K, V> {
     fn default() -> Self {
         Self {
@@ -34,21 +69,93 @@ impl<K: Ord, V> Default for OrderedMap<K, V> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<K: Ord, V> OrderedMap<K, V> {
     pub fn new() -> Self {
         Self::default()
     }
 
+    /// Create an `OrderedMap` from a `Vec` of keys that is already sorted and free of
+    /// duplicates and a `Vec` of the corresponding values without copying. Returns a
+    /// `PartsError` reporting that `keys` and `values` have different lengths or giving
+    /// the index of the first key that is out of order or a duplicate if that is not the
+    /// case. The error hands back `keys` and `values` (see `PartsError::into_input()`).
+    pub fn from_sorted_parts(keys: Vec<K>, values: Vec<V>) -> Result<Self, MapPartsError<K, V>> {
+        match PartsError::check(&keys, &values) {
+            Ok(()) => Ok(Self { keys, values }),
+            Err(error) => Err(error.with_input((keys, values))),
+        }
+    }
+
+    /// Create an `OrderedMap` from a `Vec` of keys that is already sorted and free of
+    /// duplicates and a `Vec` of the corresponding values without copying. This is not
+    /// checked (see `check_invariants()`) and the map's methods will give incorrect results
+    /// if it is not the case.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `keys` and `values` have different lengths.
+    pub fn from_sorted_parts_unchecked(keys: Vec<K>, values: Vec<V>) -> Self {
+        assert_eq!(keys.len(), values.len(), "keys and values lengths differ");
+        Self { keys, values }
+    }
+
+    /// Create an `OrderedMap` from a `Vec` of key-value pairs that is already sorted by key
+    /// and free of duplicate keys. Returns an `OrderError` giving the index of the first
+    /// pair whose key is out of order or a duplicate if that is not the case. The error
+    /// hands back `pairs` (see `OrderError::into_input()`) so that they can still be
+    /// converted with `From<Vec<(K, V)>>` without having been copied.
+    pub fn from_sorted_vec(pairs: Vec<(K, V)>) -> Result<Self, OrderError<Vec<(K, V)>>> {
+        match OrderError::check_by_key(&pairs, |(key, _)| key) {
+            Ok(()) => {
+                let (keys, values) = pairs.into_iter().unzip();
+                Ok(Self { keys, values })
+            }
+            Err(error) => Err(error.with_input(pairs)),
+        }
+    }
+
+    /// Create an `OrderedMap` from a `Vec` of key-value pairs that is already sorted by key
+    /// and free of duplicate keys. This is not checked (see `check_invariants()`) and the
+    /// map's methods will give incorrect results if it is not the case.
+    pub fn from_sorted_vec_unchecked(pairs: Vec<(K, V)>) -> Self {
+        let (keys, values) = pairs.into_iter().unzip();
+        Self { keys, values }
+    }
+
+    /// Consume the `OrderedMap` and return its keys in ascending order and the
+    /// corresponding values without copying.
+    pub fn into_parts(self) -> (Vec<K>, Vec<V>) {
+        (self.keys, self.values)
+    }
+
+    /// Return the map's keys as a slice in ascending order.
+    pub fn as_key_slice(&self) -> &[K] {
+        &self.keys
+    }
+
+    /// Return the map's values as a slice in ascending order of their keys.
+    pub fn as_value_slice(&self) -> &[V] {
+        &self.values
+    }
+
+    /// Return the map's values as a mutable slice in ascending order of their keys.
+    /// The keys cannot be changed this way so the map's order is preserved.
+    pub fn values_mut_slice(&mut self) -> &mut [V] {
+        &mut self.values
+    }
+
+    /// Check that the map's keys are sorted and contain no duplicates. This can only
+    /// fail for maps created with one of the `unchecked` constructors.
+    pub fn check_invariants(&self) -> Result<(), OrderError> {
+        OrderError::check(&self.keys)
+    }
+
     // Return true if keys is sorted and contains no duplicate keys
     // and the same length as values.
     #[cfg(test)]
     pub(crate) fn is_valid(&self) -> bool {
-        for i in 1..self.keys.len() {
-            if self.keys[i - 1] >= self.keys[i] {
-                return false;
-            }
-        }
-        self.keys.len() == self.values.len()
+        self.check_invariants().is_ok() && self.keys.len() == self.values.len()
     }
 
     /// Return the number of items in this set.
@@ -64,6 +171,28 @@ impl<K: Ord, V> OrderedMap<K, V> {
         self.keys.capacity().min(self.values.capacity())
     }
 
+    /// Tries to reserve capacity for at least `additional` more entries, returning a
+    /// `TryReserveError` instead of aborting if the allocation fails. A following `insert()`
+    /// of up to `additional` entries will not allocate.
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.keys.try_reserve(additional)?;
+        self.values.try_reserve(additional)
+    }
+
+    /// Inserts a key-value pair into the map like `insert()` but returns a `TryReserveError`
+    /// (leaving the map unchanged) instead of aborting if memory for it cannot be allocated.
+    pub fn insert_fallible(&mut self, key: K, value: V) -> Result<Option<V>, TryReserveError> {
+        match self.keys.binary_search(&key) {
+            Ok(index) => Ok(Some(core::mem::replace(&mut self.values[index], value))),
+            Err(index) => {
+                self.try_reserve(1)?;
+                self.keys.insert(index, key);
+                self.values.insert(index, value);
+                Ok(None)
+            }
+        }
+    }
+
     /// Removes all key-value pairs from the `OrderedMap` (see also: `drain()`).
     pub fn clear(&mut self) {
         self.keys.clear();
@@ -82,10 +211,15 @@ impl<K: Ord, V> OrderedMap<K, V> {
     /// Clear entries from the `OrderedMap` whose keys fall within the specified `range` and
     /// return an iterator that iterates over the removed key-values in ascending order of their
     /// keys. The iterator item type is `(K, V)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range's start is greater than its end or if its start and end are
+    /// equal and both excluded.
     pub fn drain<Q, R>(&mut self, range: R) -> MapDrain<K, V>
     where
-        Q: Ord + Sized,
-        R: std::ops::RangeBounds<Q>,
+        Q: Ord + ?Sized,
+        R: core::ops::RangeBounds<Q>,
         K: Borrow<Q>,
     {
         let (start_index, end_index) = super::range_indices(&self.keys, range);
@@ -111,10 +245,15 @@ impl<K: Ord, V> OrderedMap<K, V> {
     /// Returns an iterator visiting all key-value pairs whose key falls within the specified
     /// range in ascending order of their keys.
     /// The iterator item type is `(&'a K, &'a V)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range's start is greater than its end or if its start and end are
+    /// equal and both excluded.
     pub fn range<Q, R>(&self, range: R) -> MapIter<'_, K, V>
     where
-        Q: Ord + Sized,
-        R: std::ops::RangeBounds<Q>,
+        Q: Ord + ?Sized,
+        R: core::ops::RangeBounds<Q>,
         K: Borrow<Q>,
     {
         let (start_index, end_index) = super::range_indices(&self.keys, range);
@@ -124,13 +263,37 @@ impl<K: Ord, V> OrderedMap<K, V> {
         )
     }
 
+    /// Returns an iterator visiting all key-value pairs whose key starts with `prefix` (e.g.
+    /// `String` keys starting with a `&str`) in ascending order of their keys.
+    pub fn range_prefix<P>(&self, prefix: &P) -> MapIter<'_, K, V>
+    where
+        P: ?Sized,
+        K: Prefix<P>,
+    {
+        let (start_index, end_index) = prefix_indices(&self.keys, prefix);
+        MapIter::new(
+            &self.keys[start_index..end_index],
+            &self.values[start_index..end_index],
+        )
+    }
+
+    /// Returns an iterator visiting all key-value pairs whose tuple key has a first field
+    /// equal to `first` in ascending order of their keys.
+    pub fn range_first<Q>(&self, first: &Q) -> MapIter<'_, K, V>
+    where
+        Q: Ord + ?Sized,
+        K: FirstField<Q>,
+    {
+        self.range_prefix(first)
+    }
+
     /// Returns an iterator visiting all key-value pairs whose key falls within the specified
     /// range in ascending order of their keys, with mutable references to the values.
     /// The iterator item type is `(&'a K, &'a mut V)`.
     pub fn range_mut<Q, R>(&mut self, range: R) -> MapIterMut<'_, K, V>
     where
-        Q: Ord + Sized,
-        R: std::ops::RangeBounds<Q>,
+        Q: Ord + ?Sized,
+        R: core::ops::RangeBounds<Q>,
         K: Borrow<Q>,
     {
         let (start_index, end_index) = super::range_indices(&self.keys, range);
@@ -229,10 +392,55 @@ impl<K: Ord, V> OrderedMap<K, V> {
             Err(_) => None,
         }
     }
+
+    // Retain the entries whose key membership of `key_set` matches `keep_members` using
+    // a single linear merge of `keys` with `key_set` that moves the retained entries down
+    // into place and then truncates the keys and values.
+    fn retain_keys_membership(&mut self, key_set: &OrderedSet<K>, keep_members: bool) {
+        let mut members = key_set.members.iter().peekable();
+        let mut kept = 0;
+        for index in 0..self.keys.len() {
+            let key = &self.keys[index];
+            while members.next_if(|member| *member < key).is_some() {}
+            if (members.peek() == Some(&key)) == keep_members {
+                self.keys.swap(kept, index);
+                self.values.swap(kept, index);
+                kept += 1;
+            }
+        }
+        self.keys.truncate(kept);
+        self.values.truncate(kept);
+    }
+
+    /// Removes all entries from the `OrderedMap` whose keys are not members of `key_set`.
+    pub fn retain_keys_in(&mut self, key_set: &OrderedSet<K>) {
+        self.retain_keys_membership(key_set, true);
+    }
+
+    /// Removes all entries from the `OrderedMap` whose keys are members of `key_set`.
+    pub fn remove_keys_in(&mut self, key_set: &OrderedSet<K>) {
+        self.retain_keys_membership(key_set, false);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K: Ord + Clone, V: Clone> OrderedMap<K, V> {
+    /// Returns a new `OrderedMap` containing (copies of) the entries in the `OrderedMap`
+    /// whose keys are members of `key_set`.
+    pub fn restrict(&self, key_set: &OrderedSet<K>) -> Self {
+        self.iter().only(key_set.iter()).to_map()
+    }
+
+    /// Returns a new `OrderedMap` containing (copies of) the entries in the `OrderedMap`
+    /// whose keys are not members of `key_set`.
+    pub fn without_keys_in(&self, key_set: &OrderedSet<K>) -> Self {
+        self.iter().except(key_set.iter()).to_map()
+    }
 }
 
 /// Convert to `OrderedMap<K, V>` from a `Vec<(K, V)>`. If duplicate keys are present
 /// the last value for the key in the `Vec` is used.
+#[cfg(feature = "alloc")]
 impl<K: Ord, V> From<Vec<(K, V)>> for OrderedMap<K, V> {
     fn from(mut list: Vec<(K, V)>) -> Self {
         // sorting list first should make insertion equivalent to a push()
@@ -249,6 +457,7 @@ impl<K: Ord, V> From<Vec<(K, V)>> for OrderedMap<K, V> {
 
 /// Convert to `OrderedMap<K, V>` from a `Vec<(&'a K, &'a V)>`. If duplicate keys are present
 /// the last value for the key in the `Vec` is used.
+#[cfg(feature = "alloc")]
 impl<'a, K: Ord + Clone, V: Clone> From<Vec<(&'a K, &'a V)>> for OrderedMap<K, V> {
     fn from(mut list: Vec<(&'a K, &'a V)>) -> Self {
         // sorting list first should make insertion equivalent to a push()
@@ -265,12 +474,14 @@ impl<'a, K: Ord + Clone, V: Clone> From<Vec<(&'a K, &'a V)>> for OrderedMap<K, V
 
 /// Convert to `OrderedMap<K, V>` from a borrowed `Vec<(K, V)>`. If duplicate keys are present
 /// the last value for the key in the slice is used.
+#[cfg(feature = "alloc")]
 impl<K: Ord + Clone, V: Clone> From<&[(K, V)]> for OrderedMap<K, V> {
     fn from(list: &[(K, V)]) -> Self {
         list.to_vec().into()
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<K: Ord + Clone, V: Clone> FromIterator<(K, V)> for OrderedMap<K, V> {
     fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
         let list: Vec<(K, V)> = iter.into_iter().collect();
@@ -278,6 +489,7 @@ impl<K: Ord + Clone, V: Clone> FromIterator<(K, V)> for OrderedMap<K, V> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'a, K: 'a + Ord + Clone, V: 'a + Clone> FromIterator<&'a (K, V)> for OrderedMap<K, V> {
     fn from_iter<I: IntoIterator<Item = &'a (K, V)>>(iter: I) -> Self {
         let list: Vec<(K, V)> = iter.into_iter().cloned().collect();
@@ -285,6 +497,7 @@ impl<'a, K: 'a + Ord + Clone, V: 'a + Clone> FromIterator<&'a (K, V)> for Ordere
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'a, K: 'a + Ord + Clone, V: 'a + Clone> FromIterator<(&'a K, &'a V)> for OrderedMap<K, V> {
     fn from_iter<I: IntoIterator<Item = (&'a K, &'a V)>>(iter: I) -> Self {
         let list: Vec<(&'a K, &'a V)> = iter.into_iter().collect();
@@ -292,33 +505,116 @@ impl<'a, K: 'a + Ord + Clone, V: 'a + Clone> FromIterator<(&'a K, &'a V)> for Or
     }
 }
 
-impl<K: Ord, V> Index<K> for OrderedMap<K, V> {
+#[cfg(feature = "alloc")]
+impl<K, V, Q> Index<&Q> for OrderedMap<K, V>
+where
+    K: Ord + Borrow<Q>,
+    Q: Ord + fmt::Debug + ?Sized,
+{
     type Output = V;
 
-    fn index(&self, key: K) -> &Self::Output {
-        if let Ok(index) = self.keys.binary_search(&key) {
-            &self.values[index]
-        } else {
-            panic!("Unknown key")
+    /// Returns a reference to the value associated with `key`.
+    ///
+    /// # Panics
+    ///
+    /// Panics, naming `key` in the message, if `key` is not in the map.
+    fn index(&self, key: &Q) -> &Self::Output {
+        match self.get(key) {
+            Some(value) => value,
+            None => panic!("Unknown key {:?}", key),
         }
     }
 }
 
-impl<K: Ord, V> IndexMut<K> for OrderedMap<K, V> {
-    fn index_mut(&mut self, key: K) -> &mut Self::Output {
-        if let Ok(index) = self.keys.binary_search(&key) {
-            &mut self.values[index]
-        } else {
-            panic!("Unknown key")
+#[cfg(feature = "alloc")]
+impl<K, V, Q> IndexMut<&Q> for OrderedMap<K, V>
+where
+    K: Ord + Borrow<Q>,
+    Q: Ord + fmt::Debug + ?Sized,
+{
+    /// Returns a mutable reference to the value associated with `key`.
+    ///
+    /// # Panics
+    ///
+    /// Panics, naming `key` in the message, if `key` is not in the map.
+    fn index_mut(&mut self, key: &Q) -> &mut Self::Output {
+        match self.get_mut(key) {
+            Some(value) => value,
+            None => panic!("Unknown key {:?}", key),
         }
     }
 }
 
-#[cfg(test)]
+#[cfg(feature = "alloc")]
+impl<K: Ord + Clone, V: Clone> BitAnd<&OrderedSet<K>> for &OrderedMap<K, V> {
+    type Output = OrderedMap<K, V>;
+
+    /// Apply the & operator to return a new map containing the entries in this map
+    /// whose keys are in `key_set`.
+    fn bitand(self, key_set: &OrderedSet<K>) -> Self::Output {
+        self.restrict(key_set)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K: Ord + Clone, V: Clone> BitAnd<SetIter<'_, K>> for &OrderedMap<K, V> {
+    type Output = OrderedMap<K, V>;
+
+    /// Apply the & operator to return a new map containing the entries in this map
+    /// whose keys are in the output of `keys`.
+    fn bitand(self, keys: SetIter<'_, K>) -> Self::Output {
+        self.iter().only(keys).to_map()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K: Ord + Clone, V: Clone> Sub<&OrderedSet<K>> for &OrderedMap<K, V> {
+    type Output = OrderedMap<K, V>;
+
+    /// Apply the - operator to return a new map containing the entries in this map
+    /// whose keys are not in `key_set`.
+    fn sub(self, key_set: &OrderedSet<K>) -> Self::Output {
+        self.without_keys_in(key_set)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K: Ord + Clone, V: Clone> Sub<SetIter<'_, K>> for &OrderedMap<K, V> {
+    type Output = OrderedMap<K, V>;
+
+    /// Apply the - operator to return a new map containing the entries in this map
+    /// whose keys are not in the output of `keys`.
+    fn sub(self, keys: SetIter<'_, K>) -> Self::Output {
+        self.iter().except(keys).to_map()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K: Ord, V> BitAndAssign<&OrderedSet<K>> for OrderedMap<K, V> {
+    /// Apply the &= operator to remove any entry whose key is not in `key_set` from `self`.
+    fn bitand_assign(&mut self, key_set: &OrderedSet<K>) {
+        self.retain_keys_in(key_set)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K: Ord, V> SubAssign<&OrderedSet<K>> for OrderedMap<K, V> {
+    /// Apply the -= operator to remove any entry whose key is in `key_set` from `self`.
+    fn sub_assign(&mut self, key_set: &OrderedSet<K>) {
+        self.remove_keys_in(key_set)
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
 mod tests {
     use super::*;
-    use crate::ordered_set::ord_set_iterators::{ToList, ToSet};
-    use std::collections::HashMap;
+    use crate::ordered_map::ord_map_iterators::SkipAheadMapIterator;
+    use crate::ordered_set::ord_set_iterators::{SkipAheadIterator, ToList, ToSet};
+    use crate::test_helpers::prelude::*;
+    use core::ops::Bound;
+    use proptest::collection::vec as prop_vec;
+    use proptest::prelude::*;
+    use std::collections::{BTreeMap, BTreeSet, HashMap};
 
     static TEST_ITEMS_0: &[(&str, (&str, u32))] = &[
         ("hhh", ("HHH", 0)),
@@ -463,6 +759,20 @@ mod tests {
         assert_eq!(keys_after, keys_before - map2.keys().to_set());
     }
 
+    #[test]
+    #[should_panic(expected = "range start is greater than range end")]
+    fn range_inverted_bounds() {
+        let map: OrderedMap<&str, (&str, u32)> = TEST_ITEMS_0.into();
+        map.range::<&str, _>((Bound::Excluded("iii"), Bound::Included("ccc")));
+    }
+
+    #[test]
+    #[should_panic(expected = "range start and end are equal and excluded")]
+    fn drain_equal_excluded_bounds() {
+        let mut map: OrderedMap<&str, (&str, u32)> = TEST_ITEMS_0.into();
+        map.drain::<&str, _>((Bound::Excluded("ccc"), Bound::Excluded("ccc")));
+    }
+
     #[test]
     fn map_borrow_functionality() {
         let mut map = OrderedMap::<String, (&str, u32)>::default();
@@ -489,6 +799,53 @@ mod tests {
         }
     }
 
+    #[test]
+    fn map_borrowed_ranges_and_advance() {
+        let mut map: OrderedMap<String, u32> = TEST_ITEMS_0
+            .iter()
+            .map(|(key, (_, value))| (key.to_string(), *value))
+            .collect();
+        let keys: Vec<&String> = map
+            .range::<str, _>((Bound::Included("ccc"), Bound::Excluded("fff")))
+            .map(|(key, _)| key)
+            .collect();
+        assert_eq!(keys, vec!["ccc", "ddd", "eee"]);
+        for (_, value) in map.range_mut::<str, _>((Bound::Excluded("hhh"), Bound::Unbounded)) {
+            *value = 0;
+        }
+        assert_eq!(map.get("iii"), Some(&0));
+        let mut iter = map.iter();
+        assert_eq!(iter.advance_past_key("ddd").peek_key().unwrap(), "eee");
+        assert_eq!(iter.advance_until_key("fff").next().unwrap().0, "fff");
+        let mut keys = map.keys();
+        assert_eq!(keys.advance_until("ggg").next().unwrap(), "ggg");
+        let drained: Vec<(String, u32)> = map
+            .drain::<str, _>((Bound::Unbounded, Bound::Included("bbb")))
+            .collect();
+        assert_eq!(drained.len(), 2);
+        assert_eq!(map.keys().next().unwrap(), "ccc");
+    }
+
+    #[test]
+    fn map_prefix_ranges() {
+        let map: OrderedMap<String, u32> = ["a", "ab", "abc", "abc\u{10ffff}", "abd", "b"]
+            .iter()
+            .enumerate()
+            .map(|(i, key)| (key.to_string(), i as u32))
+            .collect();
+        let values: Vec<u32> = map.range_prefix("abc").map(|(_, value)| *value).collect();
+        assert_eq!(values, vec![2, 3]);
+        assert_eq!(map.range_prefix("ab").count(), 4);
+        assert_eq!(map.range_prefix("c").count(), 0);
+        let map: OrderedMap<(u32, u32), &str> =
+            vec![((1, 5), "a"), ((2, 1), "b"), ((2, 9), "c"), ((3, 0), "d")]
+                .into_iter()
+                .collect();
+        let values: Vec<&str> = map.range_first(&2).map(|(_, value)| *value).collect();
+        assert_eq!(values, vec!["b", "c"]);
+        assert_eq!(map.range_first(&4).next(), None);
+    }
+
     #[test]
     fn map_merge_basic() {
         let map1: OrderedMap<&str, (&str, u32)> = TEST_ITEMS_0[..5].into();
@@ -542,4 +899,211 @@ mod tests {
         let mut map2: OrderedMap<&str, (&str, u32)> = TEST_ITEMS_0[3..].into();
         for (_, _) in map1.iter_mut().merge(map2.iter_mut()) {}
     }
+
+    #[test]
+    fn map_restrict_to_key_set() {
+        let map: OrderedMap<&str, (&str, u32)> = TEST_ITEMS_0.into();
+        let key_set: OrderedSet<&str> = vec!["bbb", "lll", "mmm", "xxx"].into();
+        let restricted = map.restrict(&key_set);
+        assert!(restricted.is_valid());
+        assert_eq!(restricted.keys().to_list(), vec!["bbb", "lll", "mmm"]);
+        assert_eq!(restricted.get("lll"), map.get("lll"));
+        assert_eq!(&map & &key_set, restricted);
+        assert_eq!(&map & key_set.iter(), restricted);
+        let mut in_place = map.clone();
+        in_place.retain_keys_in(&key_set);
+        assert!(in_place.is_valid());
+        assert_eq!(in_place, restricted);
+        let mut in_place = map.clone();
+        in_place &= &key_set;
+        assert_eq!(in_place, restricted);
+    }
+
+    #[test]
+    fn map_remove_key_set() {
+        let map: OrderedMap<&str, (&str, u32)> = TEST_ITEMS_0.into();
+        let key_set: OrderedSet<&str> = vec!["bbb", "lll", "mmm", "xxx"].into();
+        let reduced = map.without_keys_in(&key_set);
+        assert!(reduced.is_valid());
+        assert_eq!(reduced.len(), map.len() - 3);
+        assert!(!reduced.contains_key("bbb"));
+        assert_eq!(reduced.get("aaa"), map.get("aaa"));
+        assert_eq!(&map - &key_set, reduced);
+        assert_eq!(&map - key_set.iter(), reduced);
+        let mut in_place = map.clone();
+        in_place.remove_keys_in(&key_set);
+        assert!(in_place.is_valid());
+        assert_eq!(in_place, reduced);
+        let mut in_place = map.clone();
+        in_place -= &key_set;
+        assert_eq!(in_place, reduced);
+    }
+
+    #[test]
+    fn map_from_sorted_parts() {
+        let map = OrderedMap::from_sorted_parts(vec!["a", "b", "c"], vec![1, 2, 3]).unwrap();
+        assert!(map.check_invariants().is_ok());
+        assert_eq!(map.get("b"), Some(&2));
+        match OrderedMap::from_sorted_parts(vec!["a", "c", "c"], vec![1, 2, 3]) {
+            Err(PartsError::Order(err)) => {
+                assert_eq!((err.index(), err.is_duplicate()), (2, true));
+                assert_eq!(err.into_input(), (vec!["a", "c", "c"], vec![1, 2, 3]));
+            }
+            _ => panic!("expected an order error"),
+        }
+        let map = OrderedMap::from_sorted_vec(vec![("a", 1), ("b", 2)]).unwrap();
+        assert_eq!(
+            map,
+            OrderedMap::from_sorted_parts(vec!["a", "b"], vec![1, 2]).unwrap()
+        );
+        let err = OrderedMap::from_sorted_vec(vec![("b", 1), ("a", 2)]).unwrap_err();
+        assert_eq!((err.index(), err.is_duplicate()), (1, false));
+        let map = OrderedMap::from(err.into_input());
+        assert_eq!(map.keys().to_list(), vec!["a", "b"]);
+        let map = OrderedMap::from_sorted_vec_unchecked(vec![("b", 1), ("a", 2)]);
+        assert!(map.check_invariants().is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "Unknown key \"zzz\"")]
+    fn map_index_unknown_key() {
+        let mut map: OrderedMap<String, u32> = OrderedMap::new();
+        map.insert("aaa".to_string(), 1);
+        map["aaa"] += 1;
+        assert_eq!(map["aaa"], 2);
+        let _ = map["zzz"];
+    }
+
+    #[test]
+    fn map_try_reserve() {
+        let mut map: OrderedMap<u32, u32> = OrderedMap::new();
+        map.try_reserve(10).unwrap();
+        assert!(map.capacity() >= 10);
+        assert!(map.try_reserve(usize::MAX).is_err());
+        let err: crate::Error = map.try_reserve(usize::MAX).unwrap_err().into();
+        assert!(matches!(err, crate::Error::TryReserve(_)));
+        assert!(map.is_empty());
+        assert_eq!(map.insert_fallible(3, 30), Ok(None));
+        assert_eq!(map.insert_fallible(1, 10), Ok(None));
+        assert_eq!(map.insert_fallible(3, 33), Ok(Some(30)));
+        assert_eq!(map.keys().to_list(), vec![1, 3]);
+        assert_eq!(map.get(&3), Some(&33));
+    }
+
+    #[test]
+    fn map_from_sorted_parts_length_mismatch() {
+        match OrderedMap::from_sorted_parts(vec!["a", "b", "c"], vec![1, 2]) {
+            Err(PartsError::Length(err)) => {
+                assert_eq!((err.keys_len(), err.values_len()), (3, 2));
+                assert_eq!(err.into_input(), (vec!["a", "b", "c"], vec![1, 2]));
+            }
+            _ => panic!("expected a length error"),
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn map_from_sorted_parts_unchecked_length_mismatch() {
+        let _ = OrderedMap::from_sorted_parts_unchecked(vec!["a", "b", "c"], vec![1, 2]);
+    }
+
+    #[test]
+    fn map_parts_and_slices() {
+        let mut map = ordered_map!(("c", 3.0), ("a", 1.0), ("b", 2.0));
+        assert_eq!(map.as_key_slice(), &["a", "b", "c"]);
+        assert_eq!(map.as_value_slice(), &[1.0, 2.0, 3.0]);
+        for value in map.values_mut_slice() {
+            *value *= 0.5;
+        }
+        assert_eq!(map.get("c"), Some(&1.5));
+        let (keys, values) = map.into_parts();
+        assert_eq!(keys, vec!["a", "b", "c"]);
+        assert_eq!(values, vec![0.5, 1.0, 1.5]);
+        assert!(OrderedMap::from_sorted_parts(keys, values).is_ok());
+    }
+
+    // Operations applied to both an `OrderedMap` and a `BTreeMap` model by `map_model`.
+    #[derive(Clone, Debug)]
+    enum MapOp {
+        Insert(u8, u32),
+        Remove(u8),
+        Entry(u8, u32),
+        Drain(u8, u8),
+        Range(u8, u8),
+        Except(Vec<u8>),
+        Only(Vec<u8>),
+        Merge(Vec<(u8, u32)>),
+    }
+
+    fn map_op() -> impl Strategy<Value = MapOp> {
+        let keys = || prop_vec(any::<u8>(), 0..48);
+        prop_oneof![
+            4 => (any::<u8>(), 0..1000_u32).prop_map(|(k, v)| MapOp::Insert(k, v)),
+            2 => any::<u8>().prop_map(MapOp::Remove),
+            2 => (any::<u8>(), 0..1000_u32).prop_map(|(k, v)| MapOp::Entry(k, v)),
+            1 => (any::<u8>(), any::<u8>()).prop_map(|(a, b)| MapOp::Drain(a, b)),
+            1 => (any::<u8>(), any::<u8>()).prop_map(|(a, b)| MapOp::Range(a, b)),
+            1 => keys().prop_map(MapOp::Except),
+            1 => keys().prop_map(MapOp::Only),
+            1 => prop_vec((any::<u8>(), 0..1000_u32), 0..48).prop_map(MapOp::Merge),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn map_model(ops in prop_vec(map_op(), 1..64)) {
+            let mut map = OrderedMap::<u8, u32>::new();
+            let mut model = BTreeMap::<u8, u32>::new();
+            for op in ops {
+                match op {
+                    MapOp::Insert(key, value) => {
+                        prop_assert_eq!(map.insert(key, value), model.insert(key, value));
+                    }
+                    MapOp::Remove(key) => prop_assert_eq!(map.remove(&key), model.remove(&key)),
+                    MapOp::Entry(key, value) => {
+                        *map.entry(key).and_modify(|v| *v += 1).or_insert(value) += 1;
+                        *model.entry(key).and_modify(|v| *v += 1).or_insert(value) += 1;
+                    }
+                    MapOp::Drain(a, b) => {
+                        let range = a.min(b)..a.max(b);
+                        let expected: Vec<(u8, u32)> =
+                            model.range(range.clone()).map(|(k, v)| (*k, *v)).collect();
+                        for (key, _) in expected.iter() {
+                            model.remove(key);
+                        }
+                        prop_assert_eq!(map.drain(range).collect::<Vec<(u8, u32)>>(), expected);
+                    }
+                    MapOp::Range(a, b) => {
+                        let range = a.min(b)..=a.max(b);
+                        let expected: Vec<(&u8, &u32)> = model.range(range.clone()).collect();
+                        prop_assert_eq!(map.range(range).collect::<Vec<(&u8, &u32)>>(), expected);
+                    }
+                    MapOp::Except(keys) => {
+                        let key_set: OrderedSet<u8> = keys.iter().collect();
+                        let key_model: BTreeSet<u8> = keys.into_iter().collect();
+                        map = map.iter().except(key_set.iter()).to_map();
+                        model.retain(|key, _| !key_model.contains(key));
+                    }
+                    MapOp::Only(keys) => {
+                        let key_set: OrderedSet<u8> = keys.iter().collect();
+                        let key_model: BTreeSet<u8> = keys.into_iter().collect();
+                        map = map.iter().only(key_set.iter()).to_map();
+                        model.retain(|key, _| key_model.contains(key));
+                    }
+                    MapOp::Merge(pairs) => {
+                        let other: OrderedMap<u8, u32> = pairs.into_iter().collect();
+                        let merged = (map.iter() | other.iter().except(map.keys())).to_map();
+                        for (key, value) in other.iter() {
+                            model.entry(*key).or_insert(*value);
+                        }
+                        map = merged;
+                    }
+                }
+                prop_assert!(map.is_valid());
+                prop_assert_eq!(map.len(), model.len());
+                let expected: Vec<(&u8, &u32)> = model.iter().collect();
+                prop_assert_eq!(map.iter().collect::<Vec<(&u8, &u32)>>(), expected);
+            }
+        }
+    }
 }
@@ -0,0 +1,299 @@
+use core::cmp::Ordering;
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::ordered_map::ord_map_iterators::SkipAheadMapIterator;
+use crate::OrderedMap;
+
+impl<K: Ord + Clone, V> OrderedMap<K, V> {
+    /// Returns a new `OrderedMap` built in a single ordered pass over the keys of this map and
+    /// `other`. The value for each key is obtained by calling `left` for keys only in this map,
+    /// `right` for keys only in `other` and `both` for keys in both maps. Keys for which the
+    /// relevant function returns `None` are omitted from the result.
+    pub fn merge_with<W, U, L, R, B>(
+        &self,
+        other: &OrderedMap<K, W>,
+        mut left: L,
+        mut right: R,
+        mut both: B,
+    ) -> OrderedMap<K, U>
+    where
+        L: FnMut(&K, &V) -> Option<U>,
+        R: FnMut(&K, &W) -> Option<U>,
+        B: FnMut(&K, &V, &W) -> Option<U>,
+    {
+        let mut keys: Vec<K> = Vec::with_capacity(self.len().max(other.len()));
+        let mut values: Vec<U> = Vec::with_capacity(self.len().max(other.len()));
+        let mut l_iter = self.iter();
+        let mut r_iter = other.iter();
+        loop {
+            let (key, o_value) = match (l_iter.peek_key(), r_iter.peek_key()) {
+                (Some(l_key), Some(r_key)) => match l_key.cmp(r_key) {
+                    Ordering::Less => {
+                        let (key, value) = l_iter.next().unwrap();
+                        (key, left(key, value))
+                    }
+                    Ordering::Greater => {
+                        let (key, value) = r_iter.next().unwrap();
+                        (key, right(key, value))
+                    }
+                    Ordering::Equal => {
+                        let (key, l_value) = l_iter.next().unwrap();
+                        let (_, r_value) = r_iter.next().unwrap();
+                        (key, both(key, l_value, r_value))
+                    }
+                },
+                (Some(_), None) => {
+                    let (key, value) = l_iter.next().unwrap();
+                    (key, left(key, value))
+                }
+                (None, Some(_)) => {
+                    let (key, value) = r_iter.next().unwrap();
+                    (key, right(key, value))
+                }
+                (None, None) => break,
+            };
+            if let Some(value) = o_value {
+                keys.push(key.clone());
+                values.push(value);
+            }
+        }
+        OrderedMap { keys, values }
+    }
+
+    /// Returns a new `OrderedMap` containing the entries whose keys are in this map or in
+    /// `other`. Values for keys in both maps are combined using `combine`.
+    pub fn union_with<F>(&self, other: &Self, mut combine: F) -> Self
+    where
+        V: Clone,
+        F: FnMut(&K, &V, &V) -> V,
+    {
+        self.merge_with(
+            other,
+            |_, value| Some(value.clone()),
+            |_, value| Some(value.clone()),
+            |key, l_value, r_value| Some(combine(key, l_value, r_value)),
+        )
+    }
+
+    /// Returns a new `OrderedMap` containing the entries whose keys are in both this map
+    /// and `other` with values obtained by combining their values using `combine`.
+    pub fn intersection_with<W, U, F>(
+        &self,
+        other: &OrderedMap<K, W>,
+        mut combine: F,
+    ) -> OrderedMap<K, U>
+    where
+        F: FnMut(&K, &V, &W) -> U,
+    {
+        let mut keys: Vec<K> = vec![];
+        let mut values: Vec<U> = vec![];
+        let mut l_iter = self.iter();
+        let mut r_iter = other.iter();
+        while let (Some(l_key), Some(r_key)) = (l_iter.peek_key(), r_iter.peek_key()) {
+            match l_key.cmp(r_key) {
+                Ordering::Less => {
+                    l_iter.advance_until_key(r_key);
+                }
+                Ordering::Greater => {
+                    r_iter.advance_until_key(l_key);
+                }
+                Ordering::Equal => {
+                    let (key, l_value) = l_iter.next().unwrap();
+                    let (_, r_value) = r_iter.next().unwrap();
+                    keys.push(key.clone());
+                    values.push(combine(key, l_value, r_value));
+                }
+            }
+        }
+        OrderedMap { keys, values }
+    }
+}
+
+impl<K: Ord, V> OrderedMap<K, V> {
+    /// Moves the entries of `other` into this map in a single ordered pass, replacing each
+    /// value by the result of calling `left` for keys only in this map, `right` for keys
+    /// only in `other` and `both` for keys in both maps. Keys for which the relevant
+    /// function returns `None` are removed. This is the in place counterpart of
+    /// `merge_with()`.
+    pub fn merge_from<W, L, R, B>(
+        &mut self,
+        other: OrderedMap<K, W>,
+        mut left: L,
+        mut right: R,
+        mut both: B,
+    ) where
+        L: FnMut(&K, V) -> Option<V>,
+        R: FnMut(&K, W) -> Option<V>,
+        B: FnMut(&K, V, W) -> Option<V>,
+    {
+        let capacity = self.len() + other.len();
+        let mut keys: Vec<K> = Vec::with_capacity(capacity);
+        let mut values: Vec<V> = Vec::with_capacity(capacity);
+        let mut l_iter = core::mem::take(&mut self.keys)
+            .into_iter()
+            .zip(core::mem::take(&mut self.values))
+            .peekable();
+        let mut r_iter = other.keys.into_iter().zip(other.values).peekable();
+        loop {
+            let ordering = match (l_iter.peek(), r_iter.peek()) {
+                (Some((l_key, _)), Some((r_key, _))) => l_key.cmp(r_key),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => break,
+            };
+            let (key, o_value) = match ordering {
+                Ordering::Less => {
+                    let (key, value) = l_iter.next().unwrap();
+                    let o_value = left(&key, value);
+                    (key, o_value)
+                }
+                Ordering::Greater => {
+                    let (key, value) = r_iter.next().unwrap();
+                    let o_value = right(&key, value);
+                    (key, o_value)
+                }
+                Ordering::Equal => {
+                    let (key, l_value) = l_iter.next().unwrap();
+                    let (_, r_value) = r_iter.next().unwrap();
+                    let o_value = both(&key, l_value, r_value);
+                    (key, o_value)
+                }
+            };
+            if let Some(value) = o_value {
+                keys.push(key);
+                values.push(value);
+            }
+        }
+        self.keys = keys;
+        self.values = values;
+    }
+
+    /// Moves all entries from `other` into this map in a single ordered pass. For keys
+    /// present in both maps `combine` is called with a mutable reference to this map's
+    /// value and `other`'s value. This is the in place counterpart of `union_with()`.
+    pub fn union_from<F>(&mut self, other: Self, mut combine: F)
+    where
+        F: FnMut(&K, &mut V, V),
+    {
+        self.merge_from(
+            other,
+            |_, value| Some(value),
+            |_, value| Some(value),
+            |key, mut l_value, r_value| {
+                combine(key, &mut l_value, r_value);
+                Some(l_value)
+            },
+        )
+    }
+
+    /// Removes the entries whose keys are not in `other` in a single ordered pass and
+    /// calls `combine` with a mutable reference to the value of each remaining entry and
+    /// `other`'s value for its key. This is the in place counterpart of
+    /// `intersection_with()`.
+    pub fn intersection_from<W, F>(&mut self, other: OrderedMap<K, W>, mut combine: F)
+    where
+        F: FnMut(&K, &mut V, W),
+    {
+        self.merge_from(
+            other,
+            |_, _| None,
+            |_, _| None,
+            |key, mut l_value, r_value| {
+                combine(key, &mut l_value, r_value);
+                Some(l_value)
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ordered_set::ord_set_iterators::ToList;
+    use crate::test_helpers::prelude::*;
+
+    #[test]
+    fn map_union_with() {
+        let map1: OrderedMap<&str, u64> = vec![("a", 1), ("c", 3), ("e", 5)].into();
+        let map2: OrderedMap<&str, u64> = vec![("b", 20), ("c", 30), ("f", 60)].into();
+        let union = map1.union_with(&map2, |_, a, b| a + b);
+        assert!(union.is_valid());
+        assert_eq!(union.keys().to_list(), vec!["a", "b", "c", "e", "f"]);
+        let values: Vec<u64> = union.values().cloned().collect();
+        assert_eq!(values, vec![1, 20, 33, 5, 60]);
+    }
+
+    #[test]
+    fn map_intersection_with() {
+        let map1: OrderedMap<&str, u64> = vec![("a", 1), ("c", 3), ("e", 5), ("g", 7)].into();
+        let map2: OrderedMap<&str, &str> = vec![("c", "C"), ("d", "D"), ("g", "G")].into();
+        let intersection = map1.intersection_with(&map2, |_, a, b| format!("{}{}", b, a));
+        assert!(intersection.is_valid());
+        assert_eq!(intersection.keys().to_list(), vec!["c", "g"]);
+        assert_eq!(intersection.get("c"), Some(&"C3".to_string()));
+        assert_eq!(intersection.get("g"), Some(&"G7".to_string()));
+    }
+
+    #[test]
+    fn map_merge_with() {
+        let map1: OrderedMap<&str, i32> = vec![("a", 1), ("c", 3), ("e", 5)].into();
+        let map2: OrderedMap<&str, i32> = vec![("b", 2), ("c", 30), ("e", 5), ("f", 6)].into();
+        let merged = map1.merge_with(
+            &map2,
+            |_, a| Some(-a),
+            |_, b| if *b % 2 == 0 { Some(*b) } else { None },
+            |_, a, b| if a == b { None } else { Some(b - a) },
+        );
+        assert!(merged.is_valid());
+        assert_eq!(merged.keys().to_list(), vec!["a", "b", "c", "f"]);
+        let values: Vec<i32> = merged.values().cloned().collect();
+        assert_eq!(values, vec![-1, 2, 27, 6]);
+    }
+
+    #[test]
+    fn map_union_from() {
+        let mut counts: OrderedMap<String, u64> = OrderedMap::new();
+        for words in &[&["x", "y", "x"][..], &["z", "x"], &["a", "z", "b"]] {
+            let mut batch: OrderedMap<String, u64> = OrderedMap::new();
+            for word in words.iter() {
+                *batch.entry(word.to_string()).or_insert(0) += 1;
+            }
+            counts.union_from(batch, |_, total, count| *total += count);
+            assert!(counts.is_valid());
+        }
+        assert_eq!(counts.keys().to_list(), vec!["a", "b", "x", "y", "z"]);
+        let values: Vec<u64> = counts.values().cloned().collect();
+        assert_eq!(values, vec![1, 1, 3, 1, 2]);
+    }
+
+    #[test]
+    fn map_intersection_from() {
+        let mut map1: OrderedMap<&str, u64> = vec![("a", 1), ("c", 3), ("e", 5), ("g", 7)].into();
+        let map2: OrderedMap<&str, &str> = vec![("c", "C"), ("d", "D"), ("g", "G")].into();
+        let expected = map1.intersection_with(&map2, |_, a, b| a * b.len() as u64 * 10);
+        map1.intersection_from(map2, |_, a, b| *a *= b.len() as u64 * 10);
+        assert!(map1.is_valid());
+        assert_eq!(map1, expected);
+        assert_eq!(map1.keys().to_list(), vec!["c", "g"]);
+    }
+
+    #[test]
+    fn map_merge_from() {
+        let mut map1: OrderedMap<&str, i32> = vec![("a", 1), ("c", 3), ("e", 5)].into();
+        let map2: OrderedMap<&str, i32> = vec![("b", 2), ("c", 30), ("e", 5), ("f", 6)].into();
+        let left = |_: &&str, a: &i32| Some(-a);
+        let right = |_: &&str, b: &i32| if *b % 2 == 0 { Some(*b) } else { None };
+        let both = |_: &&str, a: &i32, b: &i32| if a == b { None } else { Some(b - a) };
+        let expected = map1.merge_with(&map2, left, right, both);
+        map1.merge_from(
+            map2,
+            |key, a| left(key, &a),
+            |key, b| right(key, &b),
+            |key, a, b| both(key, &a, &b),
+        );
+        assert!(map1.is_valid());
+        assert_eq!(map1, expected);
+    }
+}
@@ -0,0 +1,270 @@
+use core::cmp::Ordering;
+use core::marker::PhantomData;
+
+use alloc::vec::Vec;
+
+use crate::ordered_map::ord_map_iterators::{MapIter, SkipAheadMapIterator};
+use crate::OrderedMap;
+
+/// A single difference between two `OrderedMap`s as reported by `MapDiff`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum MapDiffItem<'a, K, V> {
+    /// The key-value pair is only in the new map.
+    Added(&'a K, &'a V),
+    /// The key-value pair is only in the old map.
+    Removed(&'a K, &'a V),
+    /// The key is in both maps but its value has changed from the first to the second value.
+    Changed(&'a K, &'a V, &'a V),
+}
+
+impl<'a, K, V> MapDiffItem<'a, K, V> {
+    /// Returns the key that this difference applies to.
+    pub fn key(&self) -> &'a K {
+        match *self {
+            MapDiffItem::Added(key, _) => key,
+            MapDiffItem::Removed(key, _) => key,
+            MapDiffItem::Changed(key, _, _) => key,
+        }
+    }
+
+    /// Returns an owned `MapChange` equivalent to this difference.
+    pub fn to_change(&self) -> MapChange<K, V>
+    where
+        K: Clone,
+        V: Clone,
+    {
+        match *self {
+            MapDiffItem::Added(key, value) => MapChange::Added(key.clone(), value.clone()),
+            MapDiffItem::Removed(key, value) => MapChange::Removed(key.clone(), value.clone()),
+            MapDiffItem::Changed(key, old, new) => {
+                MapChange::Changed(key.clone(), old.clone(), new.clone())
+            }
+        }
+    }
+}
+
+/// An owned change to an `OrderedMap` suitable for replaying with `apply_diff()`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum MapChange<K, V> {
+    /// Insert the key-value pair.
+    Added(K, V),
+    /// Remove the key (and its value).
+    Removed(K, V),
+    /// Replace the key's value (the first value) with the second value.
+    Changed(K, V, V),
+}
+
+impl<K, V> MapChange<K, V> {
+    /// Returns the key that this change applies to.
+    pub fn key(&self) -> &K {
+        match self {
+            MapChange::Added(key, _) => key,
+            MapChange::Removed(key, _) => key,
+            MapChange::Changed(key, _, _) => key,
+        }
+    }
+}
+
+/// An ordered Iterator over the differences between the output of two map Iterators.
+pub struct MapDiff<'a, K, V, L, R, F>
+where
+    K: Ord,
+    L: SkipAheadMapIterator<'a, K, (&'a K, &'a V)>,
+    R: SkipAheadMapIterator<'a, K, (&'a K, &'a V)>,
+    F: FnMut(&V, &V) -> bool,
+{
+    old_iter: L,
+    new_iter: R,
+    equal: F,
+    phantom: PhantomData<(&'a K, &'a V)>,
+}
+
+impl<'a, K, V, L, R, F> MapDiff<'a, K, V, L, R, F>
+where
+    K: 'a + Ord,
+    V: 'a,
+    L: SkipAheadMapIterator<'a, K, (&'a K, &'a V)>,
+    R: SkipAheadMapIterator<'a, K, (&'a K, &'a V)>,
+    F: FnMut(&V, &V) -> bool,
+{
+    pub(crate) fn new(old_iter: L, new_iter: R, equal: F) -> Self {
+        Self {
+            old_iter,
+            new_iter,
+            equal,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<'a, K, V, L, R, F> Iterator for MapDiff<'a, K, V, L, R, F>
+where
+    K: 'a + Ord,
+    V: 'a,
+    L: SkipAheadMapIterator<'a, K, (&'a K, &'a V)>,
+    R: SkipAheadMapIterator<'a, K, (&'a K, &'a V)>,
+    F: FnMut(&V, &V) -> bool,
+{
+    type Item = MapDiffItem<'a, K, V>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(old_key) = self.old_iter.peek_key() {
+                if let Some(new_key) = self.new_iter.peek_key() {
+                    match old_key.cmp(new_key) {
+                        Ordering::Less => {
+                            let (key, value) = self.old_iter.next()?;
+                            return Some(MapDiffItem::Removed(key, value));
+                        }
+                        Ordering::Greater => {
+                            let (key, value) = self.new_iter.next()?;
+                            return Some(MapDiffItem::Added(key, value));
+                        }
+                        Ordering::Equal => {
+                            let (key, old_value) = self.old_iter.next()?;
+                            let (_, new_value) = self.new_iter.next()?;
+                            if !(self.equal)(old_value, new_value) {
+                                return Some(MapDiffItem::Changed(key, old_value, new_value));
+                            }
+                        }
+                    }
+                } else {
+                    let (key, value) = self.old_iter.next()?;
+                    return Some(MapDiffItem::Removed(key, value));
+                }
+            } else {
+                let (key, value) = self.new_iter.next()?;
+                return Some(MapDiffItem::Added(key, value));
+            }
+        }
+    }
+}
+
+/// The type of the iterator returned by `OrderedMap::diff()`.
+pub type OrderedMapDiff<'a, K, V> =
+    MapDiff<'a, K, V, MapIter<'a, K, V>, MapIter<'a, K, V>, fn(&V, &V) -> bool>;
+
+impl<K: Ord, V> OrderedMap<K, V> {
+    /// Returns an iterator over the differences between this (old) `OrderedMap` and the `new`
+    /// `OrderedMap` in ascending order of their keys.
+    pub fn diff<'a>(&'a self, new: &'a Self) -> OrderedMapDiff<'a, K, V>
+    where
+        V: PartialEq,
+    {
+        MapDiff::new(self.iter(), new.iter(), V::eq)
+    }
+
+    /// Returns an iterator over the differences between this (old) `OrderedMap` and the `new`
+    /// `OrderedMap` in ascending order of their keys using `equal` to decide whether the values
+    /// for a key in both maps are unchanged.
+    pub fn diff_by<'a, F>(
+        &'a self,
+        new: &'a Self,
+        equal: F,
+    ) -> MapDiff<'a, K, V, MapIter<'a, K, V>, MapIter<'a, K, V>, F>
+    where
+        F: FnMut(&V, &V) -> bool,
+    {
+        MapDiff::new(self.iter(), new.iter(), equal)
+    }
+
+    /// Applies the `changes` to the `OrderedMap` in a single ordered pass. If there are
+    /// multiple changes for the same key the last one takes effect. Removing a key that
+    /// is not in the map has no effect.
+    pub fn apply_diff<I>(&mut self, changes: I)
+    where
+        I: IntoIterator<Item = MapChange<K, V>>,
+    {
+        let mut changes: Vec<MapChange<K, V>> = changes.into_iter().collect();
+        // use stable sort so that the last change for any key remains last
+        changes.sort_by(|a, b| a.key().cmp(b.key()));
+        let mut keys: Vec<K> = Vec::with_capacity(self.len() + changes.len());
+        let mut values: Vec<V> = Vec::with_capacity(self.len() + changes.len());
+        let mut entries = core::mem::take(&mut self.keys)
+            .into_iter()
+            .zip(core::mem::take(&mut self.values))
+            .peekable();
+        let mut changes = changes.into_iter().peekable();
+        while let Some(change) = changes.next() {
+            if let Some(next_change) = changes.peek() {
+                if next_change.key() == change.key() {
+                    continue;
+                }
+            }
+            while let Some((key, _)) = entries.peek() {
+                match key.cmp(change.key()) {
+                    Ordering::Less => {
+                        let (key, value) = entries.next().unwrap();
+                        keys.push(key);
+                        values.push(value);
+                    }
+                    Ordering::Equal => {
+                        entries.next();
+                        break;
+                    }
+                    Ordering::Greater => break,
+                }
+            }
+            match change {
+                MapChange::Added(key, value) | MapChange::Changed(key, _, value) => {
+                    keys.push(key);
+                    values.push(value);
+                }
+                MapChange::Removed(_, _) => (),
+            }
+        }
+        for (key, value) in entries {
+            keys.push(key);
+            values.push(value);
+        }
+        self.keys = keys;
+        self.values = values;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn map_diff_works() {
+        let old: OrderedMap<&str, i32> = vec![("a", 1), ("b", 2), ("c", 3), ("e", 5)].into();
+        let new: OrderedMap<&str, i32> = vec![("b", 2), ("c", 30), ("d", 4), ("e", 5)].into();
+        let diff: Vec<MapDiffItem<&str, i32>> = old.diff(&new).collect();
+        assert_eq!(
+            diff,
+            vec![
+                MapDiffItem::Removed(&"a", &1),
+                MapDiffItem::Changed(&"c", &3, &30),
+                MapDiffItem::Added(&"d", &4),
+            ]
+        );
+        assert_eq!(old.diff(&old).count(), 0);
+        let diff: Vec<MapDiffItem<&str, i32>> =
+            old.diff_by(&new, |a, b| (a - b).abs() < 30).collect();
+        assert_eq!(
+            diff,
+            vec![MapDiffItem::Removed(&"a", &1), MapDiffItem::Added(&"d", &4)]
+        );
+    }
+
+    #[test]
+    fn map_apply_diff_works() {
+        let old: OrderedMap<&str, i32> = vec![("a", 1), ("b", 2), ("c", 3), ("e", 5)].into();
+        let new: OrderedMap<&str, i32> = vec![("b", 2), ("c", 30), ("d", 4), ("f", 6)].into();
+        let changes: Vec<MapChange<&str, i32>> =
+            old.diff(&new).map(|item| item.to_change()).collect();
+        let mut map = old.clone();
+        map.apply_diff(changes.into_iter().rev());
+        assert!(map.is_valid());
+        assert_eq!(map, new);
+        map.apply_diff(vec![
+            MapChange::Removed("b", 2),
+            MapChange::Added("b", 20),
+            MapChange::Removed("x", 0),
+        ]);
+        assert!(map.is_valid());
+        assert_eq!(map.get("b"), Some(&20));
+        assert!(!map.contains_key("x"));
+    }
+}
@@ -1,3 +1,8 @@
+use alloc::borrow::ToOwned;
+use core::borrow::Borrow;
+use core::fmt;
+use core::mem;
+
 use crate::OrderedMap;
 
 /// The data associated with an occupied `Entry`.
@@ -8,19 +13,66 @@ pub struct OccupiedEntry<'a, K: Ord, V> {
 }
 
 impl<'a, K: 'a + Ord, V: 'a> OccupiedEntry<'a, K, V> {
-    fn into_mut(self) -> &'a mut V {
+    /// Converts the entry into a mutable reference to its value.
+    pub fn into_mut(self) -> &'a mut V {
         &mut self.map.values[self.index]
     }
 
-    fn key(&self) -> &K {
+    /// Returns a reference to the entry's key.
+    pub fn key(&self) -> &K {
         &self.key
     }
 
-    fn get_mut(&mut self) -> &mut V {
+    /// Returns a reference to the entry's value.
+    pub fn get(&self) -> &V {
+        &self.map.values[self.index]
+    }
+
+    /// Returns a mutable reference to the entry's value.
+    pub fn get_mut(&mut self) -> &mut V {
         &mut self.map.values[self.index]
     }
+
+    /// Sets the entry's value and returns the old value.
+    pub fn insert(&mut self, value: V) -> V {
+        mem::replace(self.get_mut(), value)
+    }
+}
+
+/// The error returned by `OrderedMap::try_insert()` when the key is already in the map.
+/// It holds the occupied entry and the value that was not inserted.
+pub struct OccupiedError<'a, K: Ord, V> {
+    /// The entry in the map that was already occupied.
+    pub entry: OccupiedEntry<'a, K, V>,
+    /// The value which was not inserted.
+    pub value: V,
+}
+
+impl<K: Ord + fmt::Debug, V: fmt::Debug> fmt::Debug for OccupiedError<'_, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("OccupiedError")
+            .field("key", self.entry.key())
+            .field("old_value", self.entry.get())
+            .field("new_value", &self.value)
+            .finish()
+    }
 }
 
+impl<K: Ord + fmt::Debug, V: fmt::Debug> fmt::Display for OccupiedError<'_, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "failed to insert {:?}, key {:?} already exists with value {:?}",
+            self.value,
+            self.entry.key(),
+            self.entry.get(),
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: Ord + fmt::Debug, V: fmt::Debug> std::error::Error for OccupiedError<'_, K, V> {}
+
 /// The data associated with a vacant `Entry`.
 pub struct VacantEntry<'a, K: Ord, V> {
     key: K,
@@ -98,6 +150,17 @@ impl<'a, K: Ord, V: Default> Entry<'a, K, V> {
 }
 
 impl<K: Ord, V> OrderedMap<K, V> {
+    /// Inserts `value` for `key` if the key is not already in the `OrderedMap` and returns a
+    /// mutable reference to the inserted value. Otherwise the map is left unchanged and an
+    /// `OccupiedError` holding the existing entry and `value` is returned. (See
+    /// `insert_fallible()` for an insert that reports allocation failure instead.)
+    pub fn try_insert(&mut self, key: K, value: V) -> Result<&mut V, OccupiedError<'_, K, V>> {
+        match self.entry(key) {
+            Entry::Occupied(entry) => Err(OccupiedError { entry, value }),
+            Entry::Vacant(entry) => Ok(entry.insert(value)),
+        }
+    }
+
     /// Gets the `Entry` in the `OrderedMap` associated with `key` for
     /// in-place manipulation.
     pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
@@ -122,9 +185,153 @@ impl<K: Ord, V> OrderedMap<K, V> {
     }
 }
 
+/// The data associated with an occupied `EntryRef`.
+pub struct OccupiedEntryRef<'a, 'q, K: Ord, Q: ?Sized, V> {
+    key: &'q Q,
+    index: usize,
+    map: &'a mut OrderedMap<K, V>,
+}
+
+impl<'a, 'q, K: 'a + Ord, Q: ?Sized, V: 'a> OccupiedEntryRef<'a, 'q, K, Q, V> {
+    fn into_mut(self) -> &'a mut V {
+        &mut self.map.values[self.index]
+    }
+
+    fn get_mut(&mut self) -> &mut V {
+        &mut self.map.values[self.index]
+    }
+}
+
+/// The data associated with a vacant `EntryRef`.
+pub struct VacantEntryRef<'a, 'q, K: Ord, Q: ?Sized, V> {
+    key: &'q Q,
+    index: usize,
+    map: &'a mut OrderedMap<K, V>,
+}
+
+impl<'a, 'q, K, Q, V> VacantEntryRef<'a, 'q, K, Q, V>
+where
+    K: 'a + Ord,
+    Q: ?Sized + ToOwned<Owned = K>,
+    V: 'a,
+{
+    fn insert(self, value: V) -> &'a mut V {
+        self.map.keys.insert(self.index, self.key.to_owned());
+        self.map.values.insert(self.index, value);
+        &mut self.map.values[self.index]
+    }
+}
+
+/// A view into a single entry in an `OrderedMap`, found with a borrowed form of its key,
+/// which may be vacant or occupied. An owned key is only made if a value is inserted.
+pub enum EntryRef<'a, 'q, K: 'a + Ord, Q: ?Sized, V: 'a> {
+    Occupied(OccupiedEntryRef<'a, 'q, K, Q, V>),
+    Vacant(VacantEntryRef<'a, 'q, K, Q, V>),
+}
+
+impl<'a, 'q, K: Ord, Q: ?Sized, V> EntryRef<'a, 'q, K, Q, V> {
+    /// Returns a reference to the borrowed key the entry was found with.
+    pub fn key(&self) -> &'q Q {
+        match self {
+            EntryRef::Occupied(entry) => entry.key,
+            EntryRef::Vacant(entry) => entry.key,
+        }
+    }
+
+    /// Provides in-place mutable access to an occupied entry before any potential inserts
+    /// into the `OrderedMap`.
+    pub fn and_modify<F>(mut self, modify: F) -> Self
+    where
+        F: FnOnce(&mut V),
+    {
+        if let EntryRef::Occupied(entry) = &mut self {
+            modify(entry.get_mut());
+        }
+        self
+    }
+}
+
+impl<'a, 'q, K, Q, V> EntryRef<'a, 'q, K, Q, V>
+where
+    K: Ord,
+    Q: ?Sized + ToOwned<Owned = K>,
+{
+    /// Ensures the `EntryRef` is occupied by inserting `default` with an owned copy of the
+    /// key if necessary and returns a mutable reference to the value in the entry.
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        match self {
+            EntryRef::Occupied(entry) => entry.into_mut(),
+            EntryRef::Vacant(entry) => entry.insert(default),
+        }
+    }
+
+    /// Ensures the `EntryRef` is occupied by inserting the result obtained by running
+    /// `default()` if necessary and returns a mutable reference to the value in the entry.
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+        match self {
+            EntryRef::Occupied(entry) => entry.into_mut(),
+            EntryRef::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    /// Ensures the `EntryRef` is occupied by inserting the result obtained by running
+    /// `default()` on the borrowed key if necessary and returns a mutable reference to
+    /// the value in the entry.
+    pub fn or_insert_with_key<F: FnOnce(&Q) -> V>(self, default: F) -> &'a mut V {
+        match self {
+            EntryRef::Occupied(entry) => entry.into_mut(),
+            EntryRef::Vacant(entry) => {
+                let value = default(entry.key);
+                entry.insert(value)
+            }
+        }
+    }
+}
+
+impl<'a, 'q, K, Q, V> EntryRef<'a, 'q, K, Q, V>
+where
+    K: Ord,
+    Q: ?Sized + ToOwned<Owned = K>,
+    V: Default,
+{
+    /// Ensures a value is in the entry by inserting `V::default()` if vacant and returns
+    /// a mutable reference to the value in the entry.
+    pub fn or_default(self) -> &'a mut V {
+        match self {
+            EntryRef::Occupied(entry) => entry.into_mut(),
+            EntryRef::Vacant(entry) => entry.insert(V::default()),
+        }
+    }
+}
+
+impl<K: Ord, V> OrderedMap<K, V> {
+    /// Gets the `EntryRef` in the `OrderedMap` associated with the borrowed `key` for
+    /// in-place manipulation. Unlike `entry()` the key is only converted to an owned `K`
+    /// (with `ToOwned`) if a value is inserted.
+    pub fn entry_ref<'a, 'q, Q>(&'a mut self, key: &'q Q) -> EntryRef<'a, 'q, K, Q, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        match self.keys.binary_search_by_key(&key, |x| x.borrow()) {
+            Ok(index) => EntryRef::Occupied(OccupiedEntryRef {
+                key,
+                index,
+                map: self,
+            }),
+            Err(index) => EntryRef::Vacant(VacantEntryRef {
+                key,
+                index,
+                map: self,
+            }),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_helpers::prelude::*;
 
     #[test]
     fn map_entry_or_insert() {
@@ -169,4 +376,50 @@ mod tests {
         *map.entry("whatever").or_default() += 2;
         assert_eq!(map.get(&"whatever"), Some(&4_u32));
     }
+
+    #[test]
+    fn map_entry_ref() {
+        let mut map: OrderedMap<String, usize> = OrderedMap::new();
+        for word in "the cat sat on the mat by the cat".split(' ') {
+            *map.entry_ref(word).or_insert(0) += 1;
+        }
+        assert_eq!(map.get("the"), Some(&3));
+        assert_eq!(map.get("cat"), Some(&2));
+        assert_eq!(map.len(), 6);
+        assert_eq!(map.entry_ref("dog").key(), "dog");
+        assert!(!map.contains_key("dog"));
+        map.entry_ref("dog").and_modify(|e| *e += 1).or_default();
+        map.entry_ref("dog").and_modify(|e| *e += 1).or_default();
+        assert_eq!(map.get("dog"), Some(&1));
+        assert_eq!(*map.entry_ref("mouse").or_insert_with_key(str::len), 5);
+        assert_eq!(*map.entry_ref("mouse").or_insert_with(|| 0), 5);
+        let keys: Vec<&str> = map.keys().map(String::as_str).collect();
+        assert_eq!(
+            keys,
+            vec!["by", "cat", "dog", "mat", "mouse", "on", "sat", "the"]
+        );
+        let mut map: OrderedMap<Vec<u8>, u32> = OrderedMap::new();
+        map.entry_ref(&b"abc"[..]).or_insert(1);
+        assert_eq!(map.get(&b"abc"[..]), Some(&1));
+    }
+
+    #[test]
+    fn map_try_insert() {
+        let mut map: OrderedMap<&str, u32> = OrderedMap::new();
+        *map.try_insert("b", 2).unwrap() += 10;
+        assert_eq!(map.get("b"), Some(&12));
+        let err = map.try_insert("b", 3).unwrap_err();
+        assert_eq!(
+            (err.entry.key(), err.entry.get(), err.value),
+            (&"b", &12, 3)
+        );
+        assert_eq!(
+            err.to_string(),
+            "failed to insert 3, key \"b\" already exists with value 12"
+        );
+        assert_eq!(crate::Error::from(err), crate::Error::Occupied);
+        assert_eq!(map.try_insert("a", 1).ok(), Some(&mut 1));
+        let entries: Vec<(&&str, &u32)> = map.iter().collect();
+        assert_eq!(entries, vec![(&"a", &1), (&"b", &12)]);
+    }
 }
@@ -1,6 +1,6 @@
 macro_rules! after_index {
     ( $list:expr, $target:expr ) => {
-        match $list.binary_search($target) {
+        match $list.binary_search_by(|x| Ord::cmp(x.borrow(), $target)) {
             Ok(index) => index + 1,
             Err(index) => index,
         }
@@ -9,18 +9,25 @@ macro_rules! after_index {
 
 macro_rules! from_index {
     ( $list:expr, $target:expr ) => {
-        match $list.binary_search($target) {
+        match $list.binary_search_by(|x| Ord::cmp(x.borrow(), $target)) {
             Ok(index) => index,
             Err(index) => index,
         }
     };
 }
 
-use std::cmp::Ordering;
-use std::marker::PhantomData;
-use std::ops::BitOr;
-use std::slice::IterMut;
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::marker::PhantomData;
+use core::ops::BitOr;
+use core::slice::IterMut;
 
+#[cfg(feature = "alloc")]
+use alloc::vec;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "alloc")]
 use crate::OrderedMap;
 
 use crate::ordered_set::ord_set_iterators::SkipAheadIterator;
@@ -30,17 +37,26 @@ use crate::ordered_set::ord_set_iterators::SkipAheadIterator;
 /// filters.
 pub trait SkipAheadMapIterator<'a, K: 'a + Ord, I: 'a>: Iterator<Item = I> {
     /// Advance this iterator to the next item with a key after the given key
-    /// and return a pointer to this iterator.
-    fn advance_past_key(&mut self, key: &K) -> &mut Self;
+    /// and return a pointer to this iterator. The key may be given in any borrowed
+    /// form of `K` (e.g. a `&str` for `String` keys).
+    fn advance_past_key<Q>(&mut self, key: &Q) -> &mut Self
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized;
 
     /// Advance this iterator to the next item with a key at or after the given key
-    /// and return a pointer to this iterator.
-    fn advance_until_key(&mut self, key: &K) -> &mut Self;
+    /// and return a pointer to this iterator. The key may be given in any borrowed
+    /// form of `K`.
+    fn advance_until_key<Q>(&mut self, key: &Q) -> &mut Self
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized;
 
     /// Peek at the next key in the iterator without advancing the iterator.
     fn peek_key(&mut self) -> Option<&'a K>;
 }
 
+#[cfg(feature = "alloc")]
 pub trait ToMap<'a, K, V>: Iterator<Item = (&'a K, &'a V)>
 where
     K: 'a + Ord + Clone,
@@ -93,12 +109,20 @@ impl<'a, K: Ord, V> Iterator for MapIter<'a, K, V> {
 }
 
 impl<'a, K: 'a + Ord, V: 'a> SkipAheadMapIterator<'a, K, (&'a K, &'a V)> for MapIter<'a, K, V> {
-    fn advance_past_key(&mut self, k: &K) -> &mut Self {
+    fn advance_past_key<Q>(&mut self, k: &Q) -> &mut Self
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
         self.index += after_index!(self.keys[self.index..], k);
         self
     }
 
-    fn advance_until_key(&mut self, k: &K) -> &mut Self {
+    fn advance_until_key<Q>(&mut self, k: &Q) -> &mut Self
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
         self.index += from_index!(self.keys[self.index..], k);
         self
     }
@@ -108,6 +132,7 @@ impl<'a, K: 'a + Ord, V: 'a> SkipAheadMapIterator<'a, K, (&'a K, &'a V)> for Map
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'a, K: Ord + Clone, V: Clone> ToMap<'a, K, V> for MapIter<'a, K, V> {}
 
 impl<'a, K: Ord, V> MapIterFilter<'a, K, V> for MapIter<'a, K, V> {}
@@ -123,14 +148,30 @@ pub trait MapIterFilter<'a, K: 'a + Ord, V: 'a>:
     SkipAheadMapIterator<'a, K, (&'a K, &'a V)> + Sized
 {
     /// Exclude keys in the given key iterator from the output stream.
-    fn except<I: SkipAheadIterator<'a, K>>(self, iter: I) -> MapIterExcept<'a, K, V, Self, I> {
+    fn except<I: SkipAheadIterator<K>>(self, iter: I) -> MapIterExcept<'a, K, V, Self, I> {
         MapIterExcept::new(self, iter)
     }
 
     /// Exclude keys not in the given key iterator from the output stream.
-    fn only<I: SkipAheadIterator<'a, K>>(self, iter: I) -> MapIterOnly<'a, K, V, Self, I> {
+    fn only<I: SkipAheadIterator<K>>(self, iter: I) -> MapIterOnly<'a, K, V, Self, I> {
         MapIterOnly::new(self, iter)
     }
+
+    /// Exclude items whose keys do not satisfy the predicate from the output stream.
+    fn filter_keys<P: FnMut(&K) -> bool>(
+        self,
+        predicate: P,
+    ) -> MapIterFilterKeys<'a, K, V, Self, P> {
+        MapIterFilterKeys::new(self, predicate)
+    }
+
+    /// Exclude items whose values do not satisfy the predicate from the output stream.
+    fn filter_values<P: FnMut(&V) -> bool>(
+        self,
+        predicate: P,
+    ) -> MapIterFilterValues<'a, K, V, Self, P> {
+        MapIterFilterValues::new(self, predicate)
+    }
 }
 
 macro_rules! define_mapiter_filter {
@@ -140,7 +181,7 @@ macro_rules! define_mapiter_filter {
         where
             K: Ord,
             L: SkipAheadMapIterator<'a, K, (&'a K, &'a V)>,
-            R: SkipAheadIterator<'a, K>,
+            R: SkipAheadIterator<K>,
         {
             l_iter: L,
             r_iter: R,
@@ -151,7 +192,7 @@ macro_rules! define_mapiter_filter {
         where
             K: Ord,
             L: SkipAheadMapIterator<'a, K, (&'a K, &'a V)>,
-            R: SkipAheadIterator<'a, K>,
+            R: SkipAheadIterator<K>,
         {
             pub(crate) fn new(l_iter: L, r_iter: R) -> Self {
                 Self {
@@ -162,12 +203,13 @@ macro_rules! define_mapiter_filter {
             }
         }
 
+        #[cfg(feature = "alloc")]
         impl<'a, K, V, L, R> ToMap<'a, K, V> for $iter<'a, K, V, L, R>
         where
             K: Ord + Clone,
             V: Clone,
             L: SkipAheadMapIterator<'a, K, (&'a K, &'a V)>,
-            R: SkipAheadIterator<'a, K>,
+            R: SkipAheadIterator<K>,
         {
         }
     };
@@ -183,7 +225,7 @@ impl<'a, K, V, L, R> Iterator for MapIterExcept<'a, K, V, L, R>
 where
     K: Ord,
     L: SkipAheadMapIterator<'a, K, (&'a K, &'a V)>,
-    R: SkipAheadIterator<'a, K>,
+    R: SkipAheadIterator<K>,
 {
     type Item = (&'a K, &'a V);
 
@@ -217,15 +259,23 @@ impl<'a, K, V, L, R> SkipAheadMapIterator<'a, K, (&'a K, &'a V)> for MapIterExce
 where
     K: 'a + Ord,
     L: SkipAheadMapIterator<'a, K, (&'a K, &'a V)>,
-    R: SkipAheadIterator<'a, K>,
+    R: SkipAheadIterator<K>,
 {
-    fn advance_past_key(&mut self, key: &K) -> &mut Self {
+    fn advance_past_key<Q>(&mut self, key: &Q) -> &mut Self
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
         self.l_iter.advance_past_key(key);
         self.r_iter.advance_past(key);
         self
     }
 
-    fn advance_until_key(&mut self, key: &K) -> &mut Self {
+    fn advance_until_key<Q>(&mut self, key: &Q) -> &mut Self
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
         self.l_iter.advance_until_key(key);
         self.r_iter.advance_until(key);
         self
@@ -262,7 +312,7 @@ where
     K: 'a + Ord + Clone,
     V: 'a + Clone,
     L: SkipAheadMapIterator<'a, K, (&'a K, &'a V)>,
-    R: SkipAheadIterator<'a, K>,
+    R: SkipAheadIterator<K>,
 {
 }
 
@@ -276,7 +326,7 @@ impl<'a, K, V, L, R> Iterator for MapIterOnly<'a, K, V, L, R>
 where
     K: Ord,
     L: SkipAheadMapIterator<'a, K, (&'a K, &'a V)>,
-    R: SkipAheadIterator<'a, K>,
+    R: SkipAheadIterator<K>,
 {
     type Item = (&'a K, &'a V);
 
@@ -310,15 +360,23 @@ impl<'a, K, V, L, R> SkipAheadMapIterator<'a, K, (&'a K, &'a V)> for MapIterOnly
 where
     K: 'a + Ord,
     L: SkipAheadMapIterator<'a, K, (&'a K, &'a V)>,
-    R: SkipAheadIterator<'a, K>,
+    R: SkipAheadIterator<K>,
 {
-    fn advance_past_key(&mut self, key: &K) -> &mut Self {
+    fn advance_past_key<Q>(&mut self, key: &Q) -> &mut Self
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
         self.l_iter.advance_past_key(key);
         self.r_iter.advance_past(key);
         self
     }
 
-    fn advance_until_key(&mut self, key: &K) -> &mut Self {
+    fn advance_until_key<Q>(&mut self, key: &Q) -> &mut Self
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
         self.l_iter.advance_until_key(key);
         self.r_iter.advance_until(key);
         self
@@ -354,7 +412,7 @@ where
     K: 'a + Ord + Clone,
     V: 'a + Clone,
     L: SkipAheadMapIterator<'a, K, (&'a K, &'a V)>,
-    R: SkipAheadIterator<'a, K>,
+    R: SkipAheadIterator<K>,
 {
 }
 
@@ -396,7 +454,11 @@ impl<'a, K: 'a + Ord, V: 'a> SkipAheadMapIterator<'a, K, (&'a K, &'a mut V)>
     for MapIterMut<'a, K, V>
 {
     /// Advance to the item in the iterator after the selector key.
-    fn advance_past_key(&mut self, k: &K) -> &mut Self {
+    fn advance_past_key<Q>(&mut self, k: &Q) -> &mut Self
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
         let index_incr = after_index!(self.keys[self.index..], k);
         for _ in 0..index_incr {
             self.iter_mut.next();
@@ -406,7 +468,11 @@ impl<'a, K: 'a + Ord, V: 'a> SkipAheadMapIterator<'a, K, (&'a K, &'a mut V)>
     }
 
     /// Advance to the item in the iterator at or after the selector key.
-    fn advance_until_key(&mut self, k: &K) -> &mut Self {
+    fn advance_until_key<Q>(&mut self, k: &Q) -> &mut Self
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
         let index_incr = from_index!(self.keys[self.index..], k);
         for _ in 0..index_incr {
             self.iter_mut.next();
@@ -424,14 +490,30 @@ pub trait MapIterMutFilter<'a, K: 'a + Ord, V: 'a>:
     SkipAheadMapIterator<'a, K, (&'a K, &'a mut V)> + Sized
 {
     /// Exclude keys in the given key iterator from the output stream.
-    fn except<I: SkipAheadIterator<'a, K>>(self, iter: I) -> MapIterMutExcept<'a, K, V, Self, I> {
+    fn except<I: SkipAheadIterator<K>>(self, iter: I) -> MapIterMutExcept<'a, K, V, Self, I> {
         MapIterMutExcept::new(self, iter)
     }
 
     /// Exclude keys not in the given key iterator from the output stream.
-    fn only<I: SkipAheadIterator<'a, K>>(self, iter: I) -> MapIterMutOnly<'a, K, V, Self, I> {
+    fn only<I: SkipAheadIterator<K>>(self, iter: I) -> MapIterMutOnly<'a, K, V, Self, I> {
         MapIterMutOnly::new(self, iter)
     }
+
+    /// Exclude items whose keys do not satisfy the predicate from the output stream.
+    fn filter_keys<P: FnMut(&K) -> bool>(
+        self,
+        predicate: P,
+    ) -> MapIterMutFilterKeys<'a, K, V, Self, P> {
+        MapIterMutFilterKeys::new(self, predicate)
+    }
+
+    /// Exclude items whose values do not satisfy the predicate from the output stream.
+    fn filter_values<P: FnMut(&V) -> bool>(
+        self,
+        predicate: P,
+    ) -> MapIterMutFilterValues<'a, K, V, Self, P> {
+        MapIterMutFilterValues::new(self, predicate)
+    }
 }
 
 impl<'a, K: Ord, V> MapIterMutFilter<'a, K, V> for MapIterMut<'a, K, V> {}
@@ -444,7 +526,7 @@ macro_rules! define_mapitermut_filter {
             K: 'a + Ord,
             V: 'a,
             L: SkipAheadMapIterator<'a, K, (&'a K, &'a mut V)>,
-            R: SkipAheadIterator<'a, K>,
+            R: SkipAheadIterator<K>,
         {
             l_iter: L,
             r_iter: R,
@@ -456,7 +538,7 @@ macro_rules! define_mapitermut_filter {
             K: 'a + Ord,
             V: 'a,
             L: SkipAheadMapIterator<'a, K, (&'a K, &'a mut V)>,
-            R: SkipAheadIterator<'a, K>,
+            R: SkipAheadIterator<K>,
         {
             pub(crate) fn new(l_iter: L, r_iter: R) -> Self {
                 Self {
@@ -480,7 +562,7 @@ where
     K: 'a + Ord,
     V: 'a,
     L: SkipAheadMapIterator<'a, K, (&'a K, &'a mut V)>,
-    R: SkipAheadIterator<'a, K>,
+    R: SkipAheadIterator<K>,
 {
     type Item = (&'a K, &'a mut V);
 
@@ -515,15 +597,23 @@ impl<'a, K, V, L, R> SkipAheadMapIterator<'a, K, (&'a K, &'a mut V)>
 where
     K: 'a + Ord,
     L: SkipAheadMapIterator<'a, K, (&'a K, &'a mut V)>,
-    R: SkipAheadIterator<'a, K>,
+    R: SkipAheadIterator<K>,
 {
-    fn advance_past_key(&mut self, key: &K) -> &mut Self {
+    fn advance_past_key<Q>(&mut self, key: &Q) -> &mut Self
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
         self.l_iter.advance_past_key(key);
         self.r_iter.advance_past(key);
         self
     }
 
-    fn advance_until_key(&mut self, key: &K) -> &mut Self {
+    fn advance_until_key<Q>(&mut self, key: &Q) -> &mut Self
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
         self.l_iter.advance_until_key(key);
         self.r_iter.advance_until(key);
         self
@@ -566,7 +656,7 @@ where
     K: 'a + Ord,
     V: 'a,
     L: SkipAheadMapIterator<'a, K, (&'a K, &'a mut V)>,
-    R: SkipAheadIterator<'a, K>,
+    R: SkipAheadIterator<K>,
 {
     type Item = (&'a K, &'a mut V);
 
@@ -601,15 +691,23 @@ impl<'a, K, V, L, R> SkipAheadMapIterator<'a, K, (&'a K, &'a mut V)>
 where
     K: 'a + Ord,
     L: SkipAheadMapIterator<'a, K, (&'a K, &'a mut V)>,
-    R: SkipAheadIterator<'a, K>,
+    R: SkipAheadIterator<K>,
 {
-    fn advance_past_key(&mut self, key: &K) -> &mut Self {
+    fn advance_past_key<Q>(&mut self, key: &Q) -> &mut Self
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
         self.l_iter.advance_past_key(key);
         self.r_iter.advance_past(key);
         self
     }
 
-    fn advance_until_key(&mut self, key: &K) -> &mut Self {
+    fn advance_until_key<Q>(&mut self, key: &Q) -> &mut Self
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
         self.l_iter.advance_until_key(key);
         self.r_iter.advance_until(key);
         self
@@ -647,6 +745,268 @@ where
 {
 }
 
+// PREDICATE FILTERS
+
+// Define the iterators that filter the output of a map iterator whose items are
+// `(&'a K, $value)` by a predicate on their keys or values.
+macro_rules! define_map_predicate_filters {
+    ( $keys_iter:ident, $values_iter:ident, $value:ty, $filter:ident, $merge:ident ) => {
+        /// Iterator over the items of a map Iterator whose keys satisfy a predicate.
+        pub struct $keys_iter<'a, K, V, L, P>
+        where
+            K: 'a + Ord,
+            V: 'a,
+            L: SkipAheadMapIterator<'a, K, (&'a K, $value)>,
+            P: FnMut(&K) -> bool,
+        {
+            iter: L,
+            predicate: P,
+            phantom: PhantomData<(&'a K, $value)>,
+        }
+
+        impl<'a, K, V, L, P> $keys_iter<'a, K, V, L, P>
+        where
+            K: 'a + Ord,
+            V: 'a,
+            L: SkipAheadMapIterator<'a, K, (&'a K, $value)>,
+            P: FnMut(&K) -> bool,
+        {
+            pub(crate) fn new(iter: L, predicate: P) -> Self {
+                Self {
+                    iter,
+                    predicate,
+                    phantom: PhantomData,
+                }
+            }
+        }
+
+        impl<'a, K, V, L, P> Iterator for $keys_iter<'a, K, V, L, P>
+        where
+            K: 'a + Ord,
+            V: 'a,
+            L: SkipAheadMapIterator<'a, K, (&'a K, $value)>,
+            P: FnMut(&K) -> bool,
+        {
+            type Item = (&'a K, $value);
+
+            fn next(&mut self) -> Option<Self::Item> {
+                self.peek_key()?;
+                self.iter.next()
+            }
+        }
+
+        impl<'a, K, V, L, P> SkipAheadMapIterator<'a, K, (&'a K, $value)>
+            for $keys_iter<'a, K, V, L, P>
+        where
+            K: 'a + Ord,
+            V: 'a,
+            L: SkipAheadMapIterator<'a, K, (&'a K, $value)>,
+            P: FnMut(&K) -> bool,
+        {
+            fn advance_past_key<Q>(&mut self, key: &Q) -> &mut Self
+            where
+                K: Borrow<Q>,
+                Q: Ord + ?Sized,
+            {
+                self.iter.advance_past_key(key);
+                self
+            }
+
+            fn advance_until_key<Q>(&mut self, key: &Q) -> &mut Self
+            where
+                K: Borrow<Q>,
+                Q: Ord + ?Sized,
+            {
+                self.iter.advance_until_key(key);
+                self
+            }
+
+            // Rejected items are skipped by key so their values are never visited.
+            fn peek_key(&mut self) -> Option<&'a K> {
+                while let Some(key) = self.iter.peek_key() {
+                    if (self.predicate)(key) {
+                        return Some(key);
+                    }
+                    self.iter.advance_past_key(key);
+                }
+                None
+            }
+        }
+
+        impl<'a, K, V, L, P> $filter<'a, K, V> for $keys_iter<'a, K, V, L, P>
+        where
+            K: 'a + Ord,
+            V: 'a,
+            L: SkipAheadMapIterator<'a, K, (&'a K, $value)>,
+            P: FnMut(&K) -> bool,
+        {
+        }
+
+        impl<'a, K, V, L, P> $merge<'a, K, V> for $keys_iter<'a, K, V, L, P>
+        where
+            K: 'a + Ord,
+            V: 'a,
+            L: SkipAheadMapIterator<'a, K, (&'a K, $value)>,
+            P: FnMut(&K) -> bool,
+        {
+        }
+
+        /// Iterator over the items of a map Iterator whose values satisfy a predicate.
+        pub struct $values_iter<'a, K, V, L, P>
+        where
+            K: 'a + Ord,
+            V: 'a,
+            L: SkipAheadMapIterator<'a, K, (&'a K, $value)>,
+            P: FnMut(&V) -> bool,
+        {
+            iter: L,
+            predicate: P,
+            // The next accepted item if it has already been found by `peek_key`.
+            next: Option<(&'a K, $value)>,
+        }
+
+        impl<'a, K, V, L, P> $values_iter<'a, K, V, L, P>
+        where
+            K: 'a + Ord,
+            V: 'a,
+            L: SkipAheadMapIterator<'a, K, (&'a K, $value)>,
+            P: FnMut(&V) -> bool,
+        {
+            pub(crate) fn new(iter: L, predicate: P) -> Self {
+                Self {
+                    iter,
+                    predicate,
+                    next: None,
+                }
+            }
+        }
+
+        impl<'a, K, V, L, P> Iterator for $values_iter<'a, K, V, L, P>
+        where
+            K: 'a + Ord,
+            V: 'a,
+            L: SkipAheadMapIterator<'a, K, (&'a K, $value)>,
+            P: FnMut(&V) -> bool,
+        {
+            type Item = (&'a K, $value);
+
+            fn next(&mut self) -> Option<Self::Item> {
+                if let Some(item) = self.next.take() {
+                    return Some(item);
+                }
+                while let Some(item) = self.iter.next() {
+                    if (self.predicate)(&*item.1) {
+                        return Some(item);
+                    }
+                }
+                None
+            }
+        }
+
+        impl<'a, K, V, L, P> SkipAheadMapIterator<'a, K, (&'a K, $value)>
+            for $values_iter<'a, K, V, L, P>
+        where
+            K: 'a + Ord,
+            V: 'a,
+            L: SkipAheadMapIterator<'a, K, (&'a K, $value)>,
+            P: FnMut(&V) -> bool,
+        {
+            fn advance_past_key<Q>(&mut self, key: &Q) -> &mut Self
+            where
+                K: Borrow<Q>,
+                Q: Ord + ?Sized,
+            {
+                match &self.next {
+                    Some((next_key, _)) if (*next_key).borrow() > key => (),
+                    _ => {
+                        self.next = None;
+                        self.iter.advance_past_key(key);
+                    }
+                }
+                self
+            }
+
+            fn advance_until_key<Q>(&mut self, key: &Q) -> &mut Self
+            where
+                K: Borrow<Q>,
+                Q: Ord + ?Sized,
+            {
+                match &self.next {
+                    Some((next_key, _)) if (*next_key).borrow() >= key => (),
+                    _ => {
+                        self.next = None;
+                        self.iter.advance_until_key(key);
+                    }
+                }
+                self
+            }
+
+            // The values can only be seen by taking the items from the underlying
+            // iterator so the next accepted item is held until it is wanted.
+            fn peek_key(&mut self) -> Option<&'a K> {
+                if self.next.is_none() {
+                    self.next = self.next();
+                }
+                self.next.as_ref().map(|(key, _)| *key)
+            }
+        }
+
+        impl<'a, K, V, L, P> $filter<'a, K, V> for $values_iter<'a, K, V, L, P>
+        where
+            K: 'a + Ord,
+            V: 'a,
+            L: SkipAheadMapIterator<'a, K, (&'a K, $value)>,
+            P: FnMut(&V) -> bool,
+        {
+        }
+
+        impl<'a, K, V, L, P> $merge<'a, K, V> for $values_iter<'a, K, V, L, P>
+        where
+            K: 'a + Ord,
+            V: 'a,
+            L: SkipAheadMapIterator<'a, K, (&'a K, $value)>,
+            P: FnMut(&V) -> bool,
+        {
+        }
+    };
+}
+
+define_map_predicate_filters!(
+    MapIterFilterKeys,
+    MapIterFilterValues,
+    &'a V,
+    MapIterFilter,
+    MapIterMerge
+);
+
+define_map_predicate_filters!(
+    MapIterMutFilterKeys,
+    MapIterMutFilterValues,
+    &'a mut V,
+    MapIterMutFilter,
+    MapIterMutMerge
+);
+
+#[cfg(feature = "alloc")]
+impl<'a, K, V, L, P> ToMap<'a, K, V> for MapIterFilterKeys<'a, K, V, L, P>
+where
+    K: 'a + Ord + Clone,
+    V: 'a + Clone,
+    L: SkipAheadMapIterator<'a, K, (&'a K, &'a V)>,
+    P: FnMut(&K) -> bool,
+{
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, K, V, L, P> ToMap<'a, K, V> for MapIterFilterValues<'a, K, V, L, P>
+where
+    K: 'a + Ord + Clone,
+    V: 'a + Clone,
+    L: SkipAheadMapIterator<'a, K, (&'a K, &'a V)>,
+    P: FnMut(&V) -> bool,
+{
+}
+
 // VALUE ITERATOR
 
 /// An Iterator over the values in an ordered map in key order
@@ -680,12 +1040,20 @@ impl<'a, K: Ord, V> Iterator for ValueIter<'a, K, V> {
 }
 
 impl<'a, K: Ord, V> SkipAheadMapIterator<'a, K, &'a V> for ValueIter<'a, K, V> {
-    fn advance_past_key(&mut self, k: &K) -> &mut Self {
+    fn advance_past_key<Q>(&mut self, k: &Q) -> &mut Self
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
         self.index += after_index!(self.keys[self.index..], k);
         self
     }
 
-    fn advance_until_key(&mut self, k: &K) -> &mut Self {
+    fn advance_until_key<Q>(&mut self, k: &Q) -> &mut Self
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
         self.index += from_index!(self.keys[self.index..], k);
         self
     }
@@ -731,7 +1099,11 @@ impl<'a, K: Ord, V> Iterator for ValueIterMut<'a, K, V> {
 impl<'a, K: Ord, V: 'a> SkipAheadMapIterator<'a, K, &'a mut V> for ValueIterMut<'a, K, V> {
     /// Advance past items in the iterator whose keys are less than
     /// or equal to the given key
-    fn advance_past_key(&mut self, k: &K) -> &mut Self {
+    fn advance_past_key<Q>(&mut self, k: &Q) -> &mut Self
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
         let index_incr = after_index!(self.keys[self.index..], k);
         for _ in 0..index_incr {
             self.iter_mut.next();
@@ -742,7 +1114,11 @@ impl<'a, K: Ord, V: 'a> SkipAheadMapIterator<'a, K, &'a mut V> for ValueIterMut<
 
     /// Advance past items in the iterator whose keys are less than
     /// the given key
-    fn advance_until_key(&mut self, k: &K) -> &mut Self {
+    fn advance_until_key<Q>(&mut self, k: &Q) -> &mut Self
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
         let index_incr = from_index!(self.keys[self.index..], k);
         for _ in 0..index_incr {
             self.iter_mut.next();
@@ -839,13 +1215,21 @@ where
     L: SkipAheadMapIterator<'a, K, (&'a K, &'a V)>,
     R: SkipAheadMapIterator<'a, K, (&'a K, &'a V)>,
 {
-    fn advance_past_key(&mut self, k: &K) -> &mut Self {
+    fn advance_past_key<Q>(&mut self, k: &Q) -> &mut Self
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
         self.l_iter.advance_past_key(k);
         self.r_iter.advance_past_key(k);
         self
     }
 
-    fn advance_until_key(&mut self, k: &K) -> &mut Self {
+    fn advance_until_key<Q>(&mut self, k: &Q) -> &mut Self
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
         self.l_iter.advance_until_key(k);
         self.r_iter.advance_until_key(k);
         self
@@ -871,6 +1255,7 @@ where
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'a, K, V, L, R> ToMap<'a, K, V> for MapMergeIter<'a, K, V, L, R>
 where
     K: 'a + Ord + Clone,
@@ -1015,13 +1400,21 @@ where
     L: SkipAheadMapIterator<'a, K, (&'a K, &'a mut V)>,
     R: SkipAheadMapIterator<'a, K, (&'a K, &'a mut V)>,
 {
-    fn advance_past_key(&mut self, k: &K) -> &mut Self {
+    fn advance_past_key<Q>(&mut self, k: &Q) -> &mut Self
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
         self.l_iter.advance_past_key(k);
         self.r_iter.advance_past_key(k);
         self
     }
 
-    fn advance_until_key(&mut self, k: &K) -> &mut Self {
+    fn advance_until_key<Q>(&mut self, k: &Q) -> &mut Self
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
         self.l_iter.advance_until_key(k);
         self.r_iter.advance_until_key(k);
         self
@@ -1101,15 +1494,17 @@ where
 
 /// A draining iterator that removes items from an `OrderedMap` and iterates over the removed
 /// items in ascending order of their keys
+#[cfg(feature = "alloc")]
 pub struct MapDrain<'a, K: Ord, V> {
-    key_drain: std::vec::Drain<'a, K>,
-    value_drain: std::vec::Drain<'a, V>,
+    key_drain: alloc::vec::Drain<'a, K>,
+    value_drain: alloc::vec::Drain<'a, V>,
 }
 
+#[cfg(feature = "alloc")]
 impl<'a, K: Ord, V> MapDrain<'a, K, V> {
     pub(crate) fn new(
-        key_drain: std::vec::Drain<'a, K>,
-        value_drain: std::vec::Drain<'a, V>,
+        key_drain: alloc::vec::Drain<'a, K>,
+        value_drain: alloc::vec::Drain<'a, V>,
     ) -> Self {
         Self {
             key_drain,
@@ -1118,6 +1513,7 @@ impl<'a, K: Ord, V> MapDrain<'a, K, V> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'a, K: Ord, V> Iterator for MapDrain<'a, K, V> {
     type Item = (K, V);
 
@@ -1130,7 +1526,7 @@ impl<'a, K: Ord, V> Iterator for MapDrain<'a, K, V> {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "alloc"))]
 mod tests {
     use super::*;
     use crate::ordered_set::ord_set_iterators::SetIter;
@@ -1283,6 +1679,60 @@ mod tests {
         }
     }
 
+    #[test]
+    fn map_iter_filter_keys_and_values() {
+        let map = MapIter::new(LIST, VALUES)
+            .filter_keys(|key| *key > "c")
+            .filter_values(|value| value % 2 == 0)
+            .to_map();
+        assert_eq!(map.keys().cloned().collect::<Vec<_>>(), vec!["e", "i", "m"]);
+        let map = MapIter::new(LIST, VALUES)
+            .filter_values(|value| *value > 3)
+            .merge(MapIter::new(LIST_1, VALUES_1).filter_keys(|key| *key < "g"))
+            .to_map();
+        assert_eq!(
+            map.keys().cloned().collect::<Vec<_>>(),
+            vec!["a", "b", "c", "e", "f"]
+        );
+        let mut iter = MapIter::new(LIST, VALUES).filter_values(|value| value % 3 == 0);
+        assert_eq!(iter.peek_key(), Some(&"a"));
+        assert_eq!(iter.advance_until_key(&"a").peek_key(), Some(&"a"));
+        assert_eq!(iter.advance_past_key(&"a").next(), Some((&"g", &3)));
+        assert_eq!(iter.advance_until_key(&"h").next(), Some((&"m", &0)));
+        let mut iter = MapIter::new(LIST, VALUES)
+            .filter_keys(|key| *key != "e")
+            .except(SetIter::new(&["c", "i"]));
+        assert_eq!(iter.peek_key(), Some(&"a"));
+        assert_eq!(iter.advance_past_key(&"a").next(), Some((&"g", &3)));
+        let mut iter = (MapIter::new(LIST, VALUES) | MapIter::new(LIST_2, VALUES_2))
+            .filter_keys(|key| *key >= "h");
+        assert_eq!(iter.advance_past_key(&"k").next(), Some((&"m", &0)));
+        assert_eq!(iter.next(), Some((&"p", &113)));
+    }
+
+    #[test]
+    fn map_iter_mut_filter_keys_and_values() {
+        let mut map = MapIter::new(LIST, VALUES).to_map();
+        let mut map_1 = MapIter::new(LIST_1, VALUES_1).to_map();
+        for (_, value) in map
+            .iter_mut()
+            .filter_values(|value| *value < 3)
+            .merge(map_1.iter_mut().filter_keys(|key| *key > "m"))
+        {
+            *value = 100;
+        }
+        let values: Vec<i32> = map.values().cloned().collect();
+        assert_eq!(values, vec![6, 5, 4, 3, 100, 100, 100]);
+        let values: Vec<i32> = map_1.values().cloned().collect();
+        assert_eq!(values, vec![16, 15, 14, 100, 100, 100, 100]);
+        let mut iter = map.iter_mut().filter_values(|value| *value == 100);
+        assert_eq!(iter.peek_key(), Some(&"i"));
+        assert_eq!(
+            iter.advance_past_key(&"i").next().map(|(key, _)| key),
+            Some(&"k")
+        );
+    }
+
     #[test]
     fn map_iter_mut_works() {
         let mut values: Vec<i32> = VALUES.iter().cloned().collect();
@@ -2,38 +2,144 @@
 //! Useful for those situations when ordered iteration over a set's
 //! contents is a frequent requirement.
 
-use std::borrow::Borrow;
-use std::convert::From;
-use std::default::Default;
-use std::iter::FromIterator;
-use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Sub, SubAssign};
-use std::vec::Drain;
+#[cfg(feature = "alloc")]
+use core::borrow::Borrow;
+#[cfg(feature = "alloc")]
+use core::convert::From;
+#[cfg(feature = "alloc")]
+use core::default::Default;
+#[cfg(feature = "alloc")]
+use core::iter::FromIterator;
+#[cfg(feature = "alloc")]
+use core::marker::PhantomData;
+#[cfg(feature = "alloc")]
+use core::mem;
+#[cfg(feature = "alloc")]
+use core::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Sub, SubAssign};
+
+#[cfg(feature = "alloc")]
+use alloc::format;
+#[cfg(feature = "alloc")]
+use alloc::string::ToString;
+#[cfg(feature = "alloc")]
+use alloc::vec::{Drain, Vec};
 
 pub mod ord_set_iterators;
+#[cfg(feature = "alloc")]
+pub mod set_entry;
+#[cfg(feature = "alloc")]
+pub mod set_patch;
 
+#[cfg(feature = "alloc")]
 use self::ord_set_iterators::{
-    a_superset_b, are_disjoint, Difference, Intersection, SetIter, SymmetricDifference, ToList,
-    ToSet, Union,
+    a_superset_b, are_disjoint, Difference, Intersection, SetIter, SymmetricDifference, Union,
 };
 
+#[cfg(feature = "alloc")]
+pub use self::set_entry::*;
+#[cfg(feature = "alloc")]
+pub use self::set_patch::SetPatch;
+
+#[cfg(feature = "alloc")]
+use crate::error::{OrderError, TryReserveError};
+#[cfg(feature = "alloc")]
+use crate::prefix::{prefix_indices, FirstField, Prefix};
+#[cfg(feature = "alloc")]
+use crate::storage::{Heap, Storage, StorageKind};
+
 /// An set of items of type T ordered according to Ord (with no duplicates)
+/// held in the `Storage` selected by S (a `Vec<T>` by default)
+#[cfg(feature = "alloc")]
 #[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
-pub struct OrderedSet<T: Ord> {
-    pub(crate) members: Vec<T>,
+pub struct OrderedSet<T: Ord, S: StorageKind = Heap> {
+    pub(crate) members: S::Store<T>,
+    pub(crate) item_type: PhantomData<T>,
 }
 
+#[cfg(feature = "alloc")]
 impl<T: Ord> OrderedSet<T> {
     pub fn new() -> Self {
         Self::default()
     }
 
+    /// Create an `OrderedSet` from a `Vec` that is already sorted and free of duplicates
+    /// without copying. Returns an `OrderError` giving the index of the first item that
+    /// is out of order or a duplicate if that is not the case. The error hands back
+    /// `members` (see `OrderError::into_input()`) so that it can still be converted with
+    /// `From<Vec<T>>` without having been copied.
+    pub fn from_sorted_vec(members: Vec<T>) -> Result<Self, OrderError<Vec<T>>> {
+        match OrderError::check(&members) {
+            Ok(()) => Ok(Self::from_storage(members)),
+            Err(error) => Err(error.with_input(members)),
+        }
+    }
+
+    /// Create an `OrderedSet` from a `Vec` that is already sorted and free of duplicates
+    /// without copying. This is not checked (see `check_invariants()`) and the set's
+    /// methods will give incorrect results if it is not the case.
+    pub fn from_sorted_vec_unchecked(members: Vec<T>) -> Self {
+        Self::from_storage(members)
+    }
+
+    /// Tries to reserve capacity for at least `additional` more items, returning a
+    /// `TryReserveError` instead of aborting if the allocation fails.
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.members.try_reserve(additional)
+    }
+
+    /// Adds `item` to the set like `insert()` but returns a `TryReserveError` (leaving the
+    /// set unchanged) instead of aborting if memory for it cannot be allocated.
+    pub fn insert_fallible(&mut self, item: T) -> Result<bool, TryReserveError> {
+        match self.members.binary_search(&item) {
+            Ok(_) => Ok(false),
+            Err(index) => {
+                self.members.try_reserve(1)?;
+                self.members.insert(index, item);
+                Ok(true)
+            }
+        }
+    }
+
+    /// Returns a draining iterator that removes the `OrderedSet`'s elements that fall within the
+    /// given range and yields the removed elements
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range's start is greater than its end or if its start and end are
+    /// equal and both excluded.
+    pub fn drain<K, R>(&mut self, range: R) -> Drain<'_, T>
+    where
+        K: Ord + ?Sized,
+        R: core::ops::RangeBounds<K>,
+        T: Borrow<K>,
+    {
+        let (start_index, end_index) = super::range_indices(&self.members, range);
+        self.members.drain(start_index..end_index)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Ord, S: StorageKind> OrderedSet<T, S> {
+    // `members` must already be sorted and free of duplicates
+    pub(crate) fn from_storage(members: S::Store<T>) -> Self {
+        Self {
+            members,
+            item_type: PhantomData,
+        }
+    }
+
+    /// Creates an empty `OrderedSet` using a storage of type `S`.
+    pub fn new_in() -> Self {
+        Self::default()
+    }
+
     /// Return the number of items in this set.
     pub fn len(&self) -> usize {
-        self.members.len()
+        self.members.as_slice().len()
     }
 
     pub fn is_empty(&self) -> bool {
-        self.members.len() == 0
+        self.members.as_slice().is_empty()
     }
 
     pub fn capacity(&self) -> usize {
@@ -47,7 +153,7 @@ impl<T: Ord> OrderedSet<T> {
     /// Insert `item` in the set and return `false`
     /// if the item was already a member otherwise true
     pub fn insert(&mut self, item: T) -> bool {
-        if let Err(index) = self.members.binary_search(&item) {
+        if let Err(index) = self.members.as_slice().binary_search(&item) {
             self.members.insert(index, item);
             true
         } else {
@@ -62,7 +168,11 @@ impl<T: Ord> OrderedSet<T> {
         T: Borrow<Q>,
         Q: Ord + ?Sized,
     {
-        if let Ok(index) = self.members.binary_search_by_key(&item, |x| x.borrow()) {
+        if let Ok(index) = self
+            .members
+            .as_slice()
+            .binary_search_by_key(&item, |x| x.borrow())
+        {
             self.members.remove(index);
             true
         } else {
@@ -77,59 +187,114 @@ impl<T: Ord> OrderedSet<T> {
         Q: Ord + ?Sized,
     {
         self.members
+            .as_slice()
             .binary_search_by_key(&item, |x| x.borrow())
             .is_ok()
     }
 
+    /// Returns a reference to the member of the set that is equal to `item`, if any.
+    pub fn get<Q>(&self, item: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let members = self.members.as_slice();
+        let index = members.binary_search_by_key(&item, |x| x.borrow()).ok()?;
+        Some(&members[index])
+    }
+
+    /// Removes and returns the member of the set that is equal to `item`, if any.
+    pub fn take<Q>(&mut self, item: &Q) -> Option<T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let index = self
+            .members
+            .as_slice()
+            .binary_search_by_key(&item, |x| x.borrow())
+            .ok()?;
+        Some(self.members.remove(index))
+    }
+
+    /// Adds `item` to the set, replacing and returning the existing member that is equal
+    /// to it, if any.
+    pub fn replace(&mut self, item: T) -> Option<T> {
+        match self.members.as_slice().binary_search(&item) {
+            Ok(index) => Some(mem::replace(&mut self.members.as_mut_slice()[index], item)),
+            Err(index) => {
+                self.members.insert(index, item);
+                None
+            }
+        }
+    }
+
     /// Returns the `OrderedSet`'s first element in ascending order, or `None` if it is empty.
     pub fn first(&self) -> Option<&T> {
-        self.members.first()
+        self.members.as_slice().first()
     }
 
     /// Returns the `OrderedSet`'s last element in ascending order, or `None` if it is empty.
     pub fn last(&self) -> Option<&T> {
-        self.members.last()
+        self.members.as_slice().last()
     }
 
     /// Returns an iterator that iterates over the `OrderedSet`'s elements in ascending
     /// order
     pub fn iter(&self) -> SetIter<'_, T> {
-        SetIter::new(&self.members)
+        SetIter::new(self.members.as_slice())
     }
 
     /// Returns an iterator that iterates over the `OrderedSet`'s elements that fall within the
     /// given range in ascending order
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range's start is greater than its end or if its start and end are
+    /// equal and both excluded.
     pub fn range<K, R>(&self, range: R) -> SetIter<'_, T>
     where
-        K: Ord + Sized,
-        R: std::ops::RangeBounds<K>,
+        K: Ord + ?Sized,
+        R: core::ops::RangeBounds<K>,
         T: Borrow<K>,
     {
-        let (start_index, end_index) = super::range_indices(&self.members, range);
-        SetIter::new(&self.members[start_index..end_index])
+        let members = self.members.as_slice();
+        let (start_index, end_index) = super::range_indices(members, range);
+        SetIter::new(&members[start_index..end_index])
     }
 
-    /// Returns a draining iterator that removes the `OrderedSet`'s elements that fall within the
-    /// given range and yields the removed elements
-    pub fn drain<K, R>(&mut self, range: R) -> Drain<'_, T>
+    /// Returns an iterator over the `OrderedSet`'s elements that start with `prefix` (e.g.
+    /// `String` elements starting with a `&str`) in ascending order.
+    pub fn range_prefix<P>(&self, prefix: &P) -> SetIter<'_, T>
     where
-        K: Ord + Sized,
-        R: std::ops::RangeBounds<K>,
-        T: Borrow<K>,
+        P: ?Sized,
+        T: Prefix<P>,
     {
-        let (start_index, end_index) = super::range_indices(&self.members, range);
-        self.members.drain(start_index..end_index)
+        let members = self.members.as_slice();
+        let (start_index, end_index) = prefix_indices(members, prefix);
+        SetIter::new(&members[start_index..end_index])
+    }
+
+    /// Returns an iterator over the `OrderedSet`'s tuple elements whose first field is
+    /// equal to `first` in ascending order.
+    pub fn range_first<Q>(&self, first: &Q) -> SetIter<'_, T>
+    where
+        Q: Ord + ?Sized,
+        T: FirstField<Q>,
+    {
+        self.range_prefix(first)
+    }
+
+    /// Check that the set's members are sorted and contain no duplicates. This can
+    /// only fail for sets created with `from_sorted_vec_unchecked()`.
+    pub fn check_invariants(&self) -> Result<(), OrderError> {
+        OrderError::check(self.members.as_slice())
     }
 
     // Return true if members is sorted and contains no duplicates
     #[cfg(test)]
     pub(crate) fn is_valid(&self) -> bool {
-        for i in 1..self.members.len() {
-            if self.members[i - 1] >= self.members[i] {
-                return false;
-            }
-        }
-        true
+        self.check_invariants().is_ok()
     }
 
     /// Return true if this OrderedSet is disjoint from the other OrderedSet
@@ -158,13 +323,15 @@ impl<T: Ord> OrderedSet<T> {
     }
 }
 
-impl<T: Ord> Default for OrderedSet<T> {
+#[cfg(feature = "alloc")]
+impl<T: Ord, S: StorageKind> Default for OrderedSet<T, S> {
     fn default() -> Self {
-        Self { members: vec![] }
+        Self::from_storage(S::Store::<T>::default())
     }
 }
 
-impl<'a, T: Ord> IntoIterator for &'a OrderedSet<T> {
+#[cfg(feature = "alloc")]
+impl<'a, T: Ord, S: StorageKind> IntoIterator for &'a OrderedSet<T, S> {
     type Item = &'a T;
     type IntoIter = SetIter<'a, T>;
 
@@ -173,9 +340,10 @@ impl<'a, T: Ord> IntoIterator for &'a OrderedSet<T> {
     }
 }
 
-impl<T: Ord> IntoIterator for OrderedSet<T> {
+#[cfg(feature = "alloc")]
+impl<T: Ord, S: StorageKind> IntoIterator for OrderedSet<T, S> {
     type Item = T;
-    type IntoIter = ::std::vec::IntoIter<Self::Item>;
+    type IntoIter = <S::Store<T> as IntoIterator>::IntoIter;
 
     fn into_iter(self) -> Self::IntoIter {
         self.members.into_iter()
@@ -183,46 +351,48 @@ impl<T: Ord> IntoIterator for OrderedSet<T> {
 }
 
 /// Convert to OrderedSet<T> from a slice of elements
+#[cfg(feature = "alloc")]
 impl<T: Ord + Clone> From<&[T]> for OrderedSet<T> {
     fn from(list: &[T]) -> Self {
         let mut vec = list.to_vec();
         vec.sort_unstable();
         vec.dedup();
-        Self { members: vec }
+        Self::from_storage(vec)
     }
 }
 
 /// Convert to OrderedSet<T> from a slice of elements
+#[cfg(feature = "alloc")]
 impl<T: Ord + Clone> From<Vec<T>> for OrderedSet<T> {
     fn from(mut vec: Vec<T>) -> Self {
         vec.sort_unstable();
         vec.dedup();
-        Self { members: vec }
+        Self::from_storage(vec)
     }
 }
 
-impl<T: Ord> FromIterator<T> for OrderedSet<T> {
+#[cfg(feature = "alloc")]
+impl<T: Ord, S: StorageKind> FromIterator<T> for OrderedSet<T, S> {
     fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
         let mut members: Vec<T> = iter.into_iter().collect();
         members.sort_unstable();
         members.dedup();
-        Self { members }
+        Self::from_storage(members.into_iter().collect())
     }
 }
 
-impl<'a, T: 'a + Ord + Clone> FromIterator<&'a T> for OrderedSet<T> {
+#[cfg(feature = "alloc")]
+impl<'a, T: 'a + Ord + Clone, S: StorageKind> FromIterator<&'a T> for OrderedSet<T, S> {
     fn from_iter<I: IntoIterator<Item = &'a T>>(iter: I) -> Self {
-        let mut members: Vec<T> = iter.into_iter().cloned().collect();
-        members.sort_unstable();
-        members.dedup();
-        Self { members }
+        iter.into_iter().cloned().collect()
     }
 }
 
-impl<'a, T: 'a + Ord + std::fmt::Display> std::fmt::Display for OrderedSet<T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+#[cfg(feature = "alloc")]
+impl<T: Ord + core::fmt::Display, S: StorageKind> core::fmt::Display for OrderedSet<T, S> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         let mut string = "Set{".to_string();
-        for (index, item) in self.members.iter().enumerate() {
+        for (index, item) in self.members.as_slice().iter().enumerate() {
             if index == 0 {
                 string += &format!("{}", item);
             } else {
@@ -234,68 +404,70 @@ impl<'a, T: 'a + Ord + std::fmt::Display> std::fmt::Display for OrderedSet<T> {
     }
 }
 
+#[cfg(feature = "alloc")]
 macro_rules! define_set_operation {
     ( $iter:ident, $fn_doc:meta, $function:ident, $op_doc:meta, $op:ident, $op_fn:ident, $opa_doc:meta, $opa:ident, $opa_fn:ident, ) => {
-        impl<T: Ord> OrderedSet<T> {
+        impl<T: Ord, S: StorageKind> OrderedSet<T, S> {
             #[$fn_doc]
             pub fn $function<'a>(
                 &'a self,
                 other: &'a Self,
-            ) -> $iter<'a, T, SetIter<'a, T>, SetIter<'a, T>> {
+            ) -> $iter<T, SetIter<'a, T>, SetIter<'a, T>> {
                 $iter::new(self.iter(), other.iter())
             }
         }
 
-        impl<T: Ord + Clone> $op for OrderedSet<T> {
+        impl<T: Ord + Clone, S: StorageKind> $op for OrderedSet<T, S> {
             type Output = Self;
 
             #[$op_doc]
             fn $op_fn(self, other: Self) -> Self::Output {
-                self.$function(&other).to_set()
+                self.$function(&other).cloned().collect()
             }
         }
 
-        impl<T: Ord + Clone> $op for &OrderedSet<T> {
-            type Output = OrderedSet<T>;
+        impl<T: Ord + Clone, S: StorageKind> $op for &OrderedSet<T, S> {
+            type Output = OrderedSet<T, S>;
 
             #[$op_doc]
             fn $op_fn(self, other: Self) -> Self::Output {
-                self.$function(&other).to_set()
+                self.$function(&other).cloned().collect()
             }
         }
 
-        impl<T: Ord + Clone> $op<SetIter<'_, T>> for &OrderedSet<T> {
-            type Output = OrderedSet<T>;
+        impl<T: Ord + Clone, S: StorageKind> $op<SetIter<'_, T>> for &OrderedSet<T, S> {
+            type Output = OrderedSet<T, S>;
 
             #[$op_doc]
             fn $op_fn(self, other: SetIter<T>) -> Self::Output {
-                $iter::new(self.iter(), other).to_set()
+                $iter::new(self.iter(), other).cloned().collect()
             }
         }
 
-        impl<T: Ord + Clone> $opa for OrderedSet<T> {
+        impl<T: Ord + Clone, S: StorageKind> $opa for OrderedSet<T, S> {
             #[$opa_doc]
             fn $opa_fn(&mut self, other: Self) {
-                self.members = self.$function(&other).to_list();
+                self.members = self.$function(&other).cloned().collect();
             }
         }
 
-        impl<T: Ord + Clone> $opa<&Self> for OrderedSet<T> {
+        impl<T: Ord + Clone, S: StorageKind> $opa<&Self> for OrderedSet<T, S> {
             #[$opa_doc]
             fn $opa_fn(&mut self, other: &Self) {
-                self.members = self.$function(other).to_list();
+                self.members = self.$function(other).cloned().collect();
             }
         }
 
-        impl<T: Ord + Clone> $opa<SetIter<'_, T>> for OrderedSet<T> {
+        impl<T: Ord + Clone, S: StorageKind> $opa<SetIter<'_, T>> for OrderedSet<T, S> {
             #[$opa_doc]
             fn $opa_fn(&mut self, other: SetIter<'_, T>) {
-                self.members = $iter::new(self.iter(), other).to_list();
+                self.members = $iter::new(self.iter(), other).cloned().collect();
             }
         }
     };
 }
 
+#[cfg(feature = "alloc")]
 define_set_operation!(
     Difference,
     doc = "Return an ordered iterator over the set difference between this set and other
@@ -310,6 +482,7 @@ define_set_operation!(
     sub_assign,
 );
 
+#[cfg(feature = "alloc")]
 define_set_operation!(
     SymmetricDifference,
     doc = "Return an ordered iterator over the symmetric set difference between this set and other
@@ -325,6 +498,7 @@ define_set_operation!(
     bitxor_assign,
 );
 
+#[cfg(feature = "alloc")]
 define_set_operation!(
     Union,
     doc = "Return an ordered iterator over the union of this set and other
@@ -339,6 +513,7 @@ define_set_operation!(
     bitor_assign,
 );
 
+#[cfg(feature = "alloc")]
 define_set_operation!(
     Intersection,
     doc = "Return an ordered iterator over the intersection of this set and other
@@ -353,51 +528,28 @@ define_set_operation!(
     bitand_assign,
 );
 
-#[cfg(test)]
+#[cfg(all(test, feature = "alloc"))]
 mod tests {
+    use crate::test_helpers::prelude::*;
+    use core::hash::{Hash, Hasher};
+    use core::ops::Bound;
     use std::collections::hash_map::DefaultHasher;
-    use std::hash::{Hash, Hasher};
+    use std::collections::BTreeSet;
+
+    use proptest::collection::vec as prop_vec;
+    use proptest::prelude::*;
 
     use super::*;
 
     use crate::ordered_set::ord_set_iterators::{SkipAheadIterator, ToList};
+    use crate::storage::Inline;
+    use crate::test_helpers::random_sequence;
 
     static TEST_STRS: &[&str] = &[
         "hhh", "aaa", "ggg", "sss", "zzz", "bbb", "fff", "iii", "qqq", "jjj", "ddd", "eee", "ccc",
         "mmm", "lll", "nnn", "ppp", "rrr",
     ];
 
-    struct LinearCongrentialGenerator {
-        a: u64,
-        m: u64,
-        x: u64,
-    }
-
-    impl LinearCongrentialGenerator {
-        fn new(seed: u64) -> Self {
-            Self {
-                a: (1 << 18) + 1,
-                m: (1 << 35),
-                x: seed,
-            }
-        }
-    }
-
-    impl Iterator for LinearCongrentialGenerator {
-        type Item = u64;
-
-        fn next(&mut self) -> Option<Self::Item> {
-            self.x = (self.x * self.a + 1) % self.m;
-            Some(self.x)
-        }
-    }
-
-    fn random_sequence(length: usize) -> Vec<u64> {
-        LinearCongrentialGenerator::new(length as u64)
-            .take(length)
-            .collect()
-    }
-
     fn calculate_hash<T: Hash>(t: &T) -> u64 {
         let mut s = DefaultHasher::new();
         t.hash(&mut s);
@@ -436,14 +588,81 @@ mod tests {
         assert_eq!(set.range("b"..="x").to_list(), vec!["b", "h", "i", "x"]);
         assert_eq!(
             set.range::<&str, _>((
-                std::ops::Bound::Excluded("b"),
-                std::ops::Bound::Included("x")
+                core::ops::Bound::Excluded("b"),
+                core::ops::Bound::Included("x")
             ))
             .to_list(),
             vec!["h", "i", "x"]
         );
     }
 
+    #[test]
+    fn range_prefix() {
+        let set: OrderedSet<Vec<u8>> = vec![vec![1], vec![1, 2], vec![1, 255], vec![2]].into();
+        assert_eq!(
+            set.range_prefix(&[1][..]).to_list(),
+            vec![vec![1], vec![1, 2], vec![1, 255]]
+        );
+        let set: OrderedSet<(&str, u32)> = vec![("a", 1), ("b", 2), ("b", 1), ("c", 0)].into();
+        assert_eq!(set.range_first("b").to_list(), vec![("b", 1), ("b", 2)]);
+    }
+
+    // Ordered by `id` only so equal items may differ.
+    #[derive(Debug, Clone, PartialEq)]
+    struct Interned {
+        id: u32,
+        name: &'static str,
+    }
+
+    impl Eq for Interned {}
+
+    impl PartialOrd for Interned {
+        fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Interned {
+        fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+            self.id.cmp(&other.id)
+        }
+    }
+
+    impl Borrow<u32> for Interned {
+        fn borrow(&self) -> &u32 {
+            &self.id
+        }
+    }
+
+    #[test]
+    fn get_take_replace() {
+        let interned = |id, name| Interned { id, name };
+        let mut set: OrderedSet<Interned> =
+            vec![interned(1, "one"), interned(2, "two"), interned(3, "three")].into();
+        assert_eq!(set.get(&2).map(|item| item.name), Some("two"));
+        assert_eq!(set.get(&4), None);
+        let old = set.replace(interned(2, "deux"));
+        assert_eq!(old.map(|item| item.name), Some("two"));
+        assert_eq!(set.get(&2).map(|item| item.name), Some("deux"));
+        assert_eq!(set.replace(interned(0, "zero")), None);
+        assert_eq!(set.take(&3).map(|item| item.name), Some("three"));
+        assert_eq!(set.take(&3), None);
+        let names: Vec<&str> = set.iter().map(|item| item.name).collect();
+        assert_eq!(names, vec!["zero", "one", "deux"]);
+        assert!(set.is_valid());
+    }
+
+    #[test]
+    fn try_reserve_and_insert() {
+        let mut set: OrderedSet<u32> = OrderedSet::new();
+        set.try_reserve(4).unwrap();
+        assert_eq!(set.insert_fallible(3), Ok(true));
+        assert_eq!(set.insert_fallible(1), Ok(true));
+        assert_eq!(set.insert_fallible(3), Ok(false));
+        assert!(set.try_reserve(usize::MAX).is_err());
+        assert_eq!(set.iter().to_list(), vec![1, 3]);
+    }
+
     #[test]
     fn drain() {
         let mut set: OrderedSet<&str> = vec!["a", "h", "b", "z", "x", "i", "b"].into();
@@ -452,6 +671,37 @@ mod tests {
         assert_eq!(set.iter().to_list(), vec!["a", "x", "z"],)
     }
 
+    #[test]
+    fn empty_ranges() {
+        let mut set: OrderedSet<u32> = (0..10).collect();
+        assert_eq!(set.range(5..5).count(), 0);
+        assert_eq!(
+            set.range((Bound::Excluded(5), Bound::Included(5))).count(),
+            0
+        );
+        assert_eq!(
+            set.range((Bound::Excluded(4), Bound::Excluded(5))).count(),
+            0
+        );
+        assert_eq!(set.drain(5..5).count(), 0);
+        assert_eq!(set.len(), 10);
+    }
+
+    #[test]
+    #[should_panic(expected = "range start and end are equal and excluded")]
+    fn range_equal_excluded_bounds() {
+        let set: OrderedSet<u32> = (0..10).collect();
+        set.range((Bound::Excluded(5), Bound::Excluded(5)));
+    }
+
+    #[test]
+    #[should_panic(expected = "range start is greater than range end")]
+    fn drain_inverted_range() {
+        let mut set: OrderedSet<u32> = (0..10).collect();
+        #[allow(clippy::reversed_empty_ranges)]
+        set.drain(7..3);
+    }
+
     #[test]
     fn check_constraints() {
         // This is to check what constraints are required for T
@@ -784,4 +1034,147 @@ mod tests {
         set1 &= set2;
         assert_eq!(set1, set3);
     }
+
+    #[test]
+    fn inline_storage_set() {
+        let mut set = OrderedSet::<&str, Inline<8>>::new_in();
+        for text in TEST_STRS[..8].iter() {
+            assert!(set.insert(text));
+            assert!(set.is_valid());
+        }
+        assert!(!set.members.spilled());
+        assert!(!set.insert(TEST_STRS[0]));
+        assert!(set.insert(TEST_STRS[8]));
+        assert!(set.members.spilled());
+        assert!(set.is_valid());
+        assert!(set.remove(&"aaa"));
+        let other: OrderedSet<&str, Inline<8>> = TEST_STRS[4..].iter().collect();
+        let union = &set | &other;
+        assert!(union.is_valid());
+        assert_eq!(union.len(), TEST_STRS.len() - 1);
+        assert_eq!(
+            union.iter().to_list(),
+            (&set | other.iter()).iter().to_list()
+        );
+        let mut intersection = set.clone();
+        intersection &= &other;
+        assert_eq!(
+            intersection.iter().to_list(),
+            vec!["bbb", "fff", "iii", "qqq", "zzz"]
+        );
+        assert!(intersection.is_subset(&set));
+        let items: Vec<&str> = intersection.into_iter().collect();
+        assert_eq!(items, vec!["bbb", "fff", "iii", "qqq", "zzz"]);
+    }
+
+    #[test]
+    fn from_sorted_vec() {
+        let set = OrderedSet::from_sorted_vec(vec!["aaa", "bbb", "ccc"]).unwrap();
+        assert!(set.check_invariants().is_ok());
+        assert_eq!(set.iter().to_list(), vec!["aaa", "bbb", "ccc"]);
+        let err = OrderedSet::from_sorted_vec(vec!["aaa", "ccc", "bbb"]).unwrap_err();
+        assert_eq!((err.index(), err.is_duplicate()), (2, false));
+        assert_eq!(err.to_string(), "item at index 2 is out of order");
+        let err = OrderedSet::from_sorted_vec(vec![1, 2, 2, 3]).unwrap_err();
+        assert_eq!((err.index(), err.is_duplicate()), (2, true));
+        let err = OrderedSet::from_sorted_vec(vec![3, 1, 2]).unwrap_err();
+        assert_eq!(
+            OrderedSet::from(err.into_input()).iter().to_list(),
+            vec![1, 2, 3]
+        );
+        let set = OrderedSet::from_sorted_vec_unchecked(vec![3, 2, 1]);
+        assert_eq!(set.check_invariants().map_err(|err| err.index()), Err(1));
+    }
+
+    // Operations applied to both an `OrderedSet` and a `BTreeSet` model by `set_model`.
+    #[derive(Clone, Debug)]
+    enum SetOp {
+        Insert(u8),
+        Remove(u8),
+        Drain(u8, u8),
+        Range(u8, u8),
+        Compare(Vec<u8>),
+        Union(Vec<u8>),
+        Intersection(Vec<u8>),
+        Difference(Vec<u8>),
+        SymmetricDifference(Vec<u8>),
+    }
+
+    fn set_op() -> impl Strategy<Value = SetOp> {
+        let items = || prop_vec(any::<u8>(), 0..48);
+        prop_oneof![
+            4 => any::<u8>().prop_map(SetOp::Insert),
+            2 => any::<u8>().prop_map(SetOp::Remove),
+            1 => (any::<u8>(), any::<u8>()).prop_map(|(a, b)| SetOp::Drain(a, b)),
+            1 => (any::<u8>(), any::<u8>()).prop_map(|(a, b)| SetOp::Range(a, b)),
+            1 => items().prop_map(SetOp::Compare),
+            1 => items().prop_map(SetOp::Union),
+            1 => items().prop_map(SetOp::Intersection),
+            1 => items().prop_map(SetOp::Difference),
+            1 => items().prop_map(SetOp::SymmetricDifference),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn set_model(ops in prop_vec(set_op(), 1..64)) {
+            let mut set = OrderedSet::<u8>::new();
+            let mut model = BTreeSet::<u8>::new();
+            for op in ops {
+                match op {
+                    SetOp::Insert(item) => prop_assert_eq!(set.insert(item), model.insert(item)),
+                    SetOp::Remove(item) => prop_assert_eq!(set.remove(&item), model.remove(&item)),
+                    SetOp::Drain(a, b) => {
+                        let range = a.min(b)..a.max(b);
+                        let expected: Vec<u8> = model.range(range.clone()).cloned().collect();
+                        for item in expected.iter() {
+                            model.remove(item);
+                        }
+                        prop_assert_eq!(set.drain(range).collect::<Vec<u8>>(), expected);
+                    }
+                    SetOp::Range(a, b) => {
+                        let range = a.min(b)..=a.max(b);
+                        let expected: Vec<u8> = model.range(range.clone()).cloned().collect();
+                        prop_assert_eq!(set.range(range).to_list(), expected);
+                    }
+                    SetOp::Compare(items) => {
+                        let other: OrderedSet<u8> = items.iter().collect();
+                        let other_model: BTreeSet<u8> = items.into_iter().collect();
+                        prop_assert_eq!(set.is_subset(&other), model.is_subset(&other_model));
+                        prop_assert_eq!(set.is_superset(&other), model.is_superset(&other_model));
+                        prop_assert_eq!(set.is_disjoint(&other), model.is_disjoint(&other_model));
+                    }
+                    SetOp::Union(items) => {
+                        let other: OrderedSet<u8> = items.iter().collect();
+                        let other_model: BTreeSet<u8> = items.into_iter().collect();
+                        set = &set | &other;
+                        model = &model | &other_model;
+                    }
+                    SetOp::Intersection(items) => {
+                        let other: OrderedSet<u8> = items.iter().collect();
+                        let other_model: BTreeSet<u8> = items.into_iter().collect();
+                        set &= &other;
+                        model = &model & &other_model;
+                    }
+                    SetOp::Difference(items) => {
+                        let other: OrderedSet<u8> = items.iter().collect();
+                        let other_model: BTreeSet<u8> = items.into_iter().collect();
+                        set -= other;
+                        model = &model - &other_model;
+                    }
+                    SetOp::SymmetricDifference(items) => {
+                        let other: OrderedSet<u8> = items.iter().collect();
+                        let other_model: BTreeSet<u8> = items.into_iter().collect();
+                        set ^= other.iter();
+                        model = &model ^ &other_model;
+                    }
+                }
+                prop_assert!(set.is_valid());
+                prop_assert_eq!(set.len(), model.len());
+                prop_assert_eq!(set.first(), model.first());
+                prop_assert_eq!(set.last(), model.last());
+                prop_assert_eq!(set.iter().to_list(), model.iter().cloned().collect::<Vec<u8>>());
+            }
+        }
+    }
 }
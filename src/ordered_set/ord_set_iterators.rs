@@ -1,6 +1,6 @@
 macro_rules! after_index {
     ( $list:expr, $target:expr ) => {
-        match $list.binary_search($target) {
+        match $list.binary_search_by(|x| Ord::cmp(x.borrow(), $target)) {
             Ok(index) => index + 1,
             Err(index) => index,
         }
@@ -9,35 +9,53 @@ macro_rules! after_index {
 
 macro_rules! from_index {
     ( $list:expr, $target:expr ) => {
-        match $list.binary_search($target) {
+        match $list.binary_search_by(|x| Ord::cmp(x.borrow(), $target)) {
             Ok(index) => index,
             Err(index) => index,
         }
     };
 }
 
-use std::cmp::Ordering;
-use std::marker::PhantomData;
-use std::ops::{BitAnd, BitOr, BitXor, Sub};
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::marker::PhantomData;
+use core::ops::{BitAnd, BitOr, BitXor, Range, Sub};
 
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "alloc")]
 use crate::OrderedSet;
 
 /// Iterator enhancement to provide peek and advance ahead features. This mechanism
 /// is used to optimise implementation of set operation (difference, intersection, etc)
 /// iterators.
-pub trait SkipAheadIterator<'a, T: 'a + Ord>: Iterator<Item = &'a T> {
+///
+/// The iterator's items may be references to `T` (as for the crate's own collections)
+/// or owned values (e.g. generated or read from a file) as long as `peek` can expose
+/// the next one as a `&T`.
+pub trait SkipAheadIterator<T: Ord>: Iterator {
     /// Peek at the next item in the iterator without advancing the iterator.
-    fn peek(&mut self) -> Option<&'a T>;
+    fn peek(&mut self) -> Option<&T>;
 
     /// Advance this iterator to the next item after the given item and
-    /// return a pointer to this iterator.
-    fn advance_past(&mut self, t: &T) -> &mut Self;
+    /// return a pointer to this iterator. The item may be given in any borrowed
+    /// form of `T` (e.g. a `&str` for `String` items).
+    fn advance_past<Q>(&mut self, t: &Q) -> &mut Self
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized;
 
     /// Advance this iterator to the next item at or after the given item and
-    /// return a pointer to this iterator.
-    fn advance_until(&mut self, t: &T) -> &mut Self;
+    /// return a pointer to this iterator. The item may be given in any borrowed
+    /// form of `T`.
+    fn advance_until<Q>(&mut self, t: &Q) -> &mut Self
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized;
 }
 
+#[cfg(feature = "alloc")]
 pub trait ToList<'a, T>: Iterator<Item = &'a T>
 where
     T: 'a + Clone,
@@ -48,19 +66,22 @@ where
     }
 }
 
+#[cfg(feature = "alloc")]
 pub trait ToSet<'a, T>: ToList<'a, T>
 where
     T: 'a + Ord + Clone,
 {
     /// Create a OrderedSet<T> from the elements in the Iterator's output
     fn to_set(&mut self) -> OrderedSet<T> {
-        OrderedSet::<T> {
-            members: self.to_list(),
-        }
+        OrderedSet::from_storage(self.to_list())
     }
 }
 
 // SELECTION ITERATOR
+
+/// An Iterator over the items of another Iterator that satisfy a predicate. If the
+/// underlying Iterator is a `SkipAheadIterator` then so is the `Selector` so the
+/// selected items may be combined with other sets using the set operations.
 pub struct Selector<'a, T, I, P>
 where
     T: 'a + Ord,
@@ -69,6 +90,8 @@ where
 {
     iter: I,
     predicate: P,
+    // The next selected item if it has already been found by `peek`.
+    next: Option<&'a T>,
 }
 
 impl<'a, T, I, P> Iterator for Selector<'a, T, I, P>
@@ -80,6 +103,9 @@ where
     type Item = &'a T;
 
     fn next(&mut self) -> Option<Self::Item> {
+        if let Some(element) = self.next.take() {
+            return Some(element);
+        }
         while let Some(element) = self.iter.next() {
             if (self.predicate)(&element) {
                 return Some(element);
@@ -89,6 +115,59 @@ where
     }
 }
 
+impl<'a, T, I, P> SkipAheadIterator<T> for Selector<'a, T, I, P>
+where
+    T: 'a + Ord,
+    I: SkipAheadIterator<T, Item = &'a T>,
+    P: FnMut(&I::Item) -> bool,
+{
+    fn peek(&mut self) -> Option<&T> {
+        if self.next.is_none() {
+            self.next = self.next();
+        }
+        self.next
+    }
+
+    fn advance_past<Q>(&mut self, t: &Q) -> &mut Self
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        match self.next {
+            Some(element) if element.borrow() > t => (),
+            _ => {
+                self.next = None;
+                self.iter.advance_past(t);
+            }
+        }
+        self
+    }
+
+    fn advance_until<Q>(&mut self, t: &Q) -> &mut Self
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        match self.next {
+            Some(element) if element.borrow() >= t => (),
+            _ => {
+                self.next = None;
+                self.iter.advance_until(t);
+            }
+        }
+        self
+    }
+}
+
+impl<'a, T, I, P> IterSetOperations<T> for Selector<'a, T, I, P>
+where
+    T: 'a + Ord,
+    I: SkipAheadIterator<T, Item = &'a T>,
+    P: FnMut(&I::Item) -> bool,
+{
+}
+
+#[cfg(feature = "alloc")]
 impl<'a, T, I, P> ToList<'a, T> for Selector<'a, T, I, P>
 where
     T: 'a + Ord + Clone,
@@ -97,6 +176,7 @@ where
 {
 }
 
+#[cfg(feature = "alloc")]
 impl<'a, T, I, P> ToSet<'a, T> for Selector<'a, T, I, P>
 where
     T: 'a + Ord + Clone,
@@ -105,6 +185,66 @@ where
 {
 }
 
+impl<'a, T, I, P> Selection<'a, T> for Selector<'a, T, I, P>
+where
+    T: 'a + Ord + Clone,
+    I: Iterator<Item = &'a T>,
+    P: FnMut(&I::Item) -> bool,
+{
+}
+
+macro_rules! impl_op_for_selector {
+    ( $op:ident, $op_fn:ident, $output:ident, [ $( $item:tt )* ], $doc:meta ) => {
+        impl<'a, T, I, P, J> $op<J> for Selector<'a, T, I, P>
+        where
+            T: 'a + Ord,
+            I: SkipAheadIterator<T, Item = &'a T>,
+            P: FnMut(&I::Item) -> bool,
+            J: SkipAheadIterator<T, $( $item )*>,
+        {
+            type Output = $output<T, Self, J>;
+
+            #[$doc]
+            fn $op_fn(self, other: J) -> Self::Output {
+                $output::new(self, other)
+            }
+        }
+    };
+}
+
+impl_op_for_selector!(
+    BitOr,
+    bitor,
+    Union,
+    [Item = &'a T],
+    doc = "Return a new ordered iterator over the set union of the selected
+    elements and other."
+);
+impl_op_for_selector!(
+    BitAnd,
+    bitand,
+    Intersection,
+    [],
+    doc = "Return a new ordered iterator over the set intersection of the selected
+    elements and other."
+);
+impl_op_for_selector!(
+    BitXor,
+    bitxor,
+    SymmetricDifference,
+    [Item = &'a T],
+    doc = "Return a new ordered iterator over the symmetric set difference
+    between the selected elements and other."
+);
+impl_op_for_selector!(
+    Sub,
+    sub,
+    Difference,
+    [],
+    doc = "Return a new ordered iterator over the set difference of the selected
+    elements and other."
+);
+
 pub trait Selection<'a, T>: Iterator<Item = &'a T>
 where
     T: 'a + Ord + Clone,
@@ -117,6 +257,7 @@ where
         Selector {
             iter: self,
             predicate,
+            next: None,
         }
     }
 }
@@ -148,39 +289,49 @@ impl<'a, T: Ord> Iterator for SetIter<'a, T> {
     }
 }
 
-impl<'a, T: 'a + Ord> SkipAheadIterator<'a, T> for SetIter<'a, T> {
-    fn advance_past(&mut self, t: &T) -> &mut Self {
+impl<'a, T: 'a + Ord> SkipAheadIterator<T> for SetIter<'a, T> {
+    fn advance_past<Q>(&mut self, t: &Q) -> &mut Self
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
         self.index += after_index!(self.elements[self.index..], t);
         self
     }
 
-    fn advance_until(&mut self, t: &T) -> &mut Self {
+    fn advance_until<Q>(&mut self, t: &Q) -> &mut Self
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
         self.index += from_index!(self.elements[self.index..], t);
         self
     }
 
-    fn peek(&mut self) -> Option<&'a T> {
+    fn peek(&mut self) -> Option<&T> {
         self.elements.get(self.index)
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'a, T: Ord + Clone> ToList<'a, T> for SetIter<'a, T> {}
 
+#[cfg(feature = "alloc")]
 impl<'a, T: Ord + Clone> ToSet<'a, T> for SetIter<'a, T> {}
 
-impl<'a, T: Ord + Clone> IterSetOperations<'a, T> for SetIter<'a, T> {}
+impl<'a, T: Ord + Clone> IterSetOperations<T> for SetIter<'a, T> {}
 
 impl<'a, T: Ord + Clone> Selection<'a, T> for SetIter<'a, T> {}
 
 macro_rules! impl_op_for_set_iter {
-    ( $op:ident, $op_fn:ident, $output:ident, $doc:meta ) => {
+    ( $op:ident, $op_fn:ident, $output:ident, [ $( $item:tt )* ], $doc:meta ) => {
         impl<'a, T, I> $op<I> for SetIter<'a, T>
         where
             T: Ord,
             Self: Sized,
-            I: SkipAheadIterator<'a, T>,
+            I: SkipAheadIterator<T, $( $item )*>,
         {
-            type Output = $output<'a, T, Self, I>;
+            type Output = $output<T, Self, I>;
 
             #[$doc]
             fn $op_fn(self, other: I) -> Self::Output {
@@ -194,6 +345,7 @@ impl_op_for_set_iter!(
     BitOr,
     bitor,
     Union,
+    [Item = &'a T],
     doc = "Return a new ordered iterator over the set union of the contents
     of this iterator and other i.e. elements that appear in this iterator
     or other."
@@ -202,6 +354,7 @@ impl_op_for_set_iter!(
     BitAnd,
     bitand,
     Intersection,
+    [],
     doc = "Return a new ordered iterator over the set intersection of the contents
     of this iterator and other i.e. elements that appear in both this iterator
     and other."
@@ -210,6 +363,7 @@ impl_op_for_set_iter!(
     BitXor,
     bitxor,
     SymmetricDifference,
+    [Item = &'a T],
     doc = "Return a new ordered iterator over the symmetric set difference
     between the contents of this iterator and other i.e. elements that appear
     in this iterator or other but not both."
@@ -218,84 +372,150 @@ impl_op_for_set_iter!(
     Sub,
     sub,
     Difference,
+    [],
     doc = "Return a new ordered iterator over the set difference of the contents
     of this iterator and other i.e. elements that appear in this iterator
     but not other."
 );
 
-pub trait IterSetOperations<'a, T>: SkipAheadIterator<'a, T> + Sized
+/// Set operations on `SkipAheadIterator`s. The union and symmetric difference take their
+/// items from both iterators so they must have the same item type while the intersection
+/// and difference yield this iterator's items and accept any other `SkipAheadIterator<T>`.
+pub trait IterSetOperations<T>: SkipAheadIterator<T> + Sized
 where
-    T: 'a + Ord,
+    T: Ord,
 {
     /// Iterate over the set union of this Iterator and the given Iterator
     /// in the order defined by their elements `Ord` trait implementation.
-    fn union<I: SkipAheadIterator<'a, T>>(self, iter: I) -> Union<'a, T, Self, I> {
+    fn union<I>(self, iter: I) -> Union<T, Self, I>
+    where
+        I: SkipAheadIterator<T, Item = Self::Item>,
+    {
         Union::new(self, iter)
     }
 
     /// Iterate over the set intersection of this Iterator and the given Iterator
     /// in the order defined by their elements `Ord` trait implementation.
-    fn intersection<I: SkipAheadIterator<'a, T>>(self, iter: I) -> Intersection<'a, T, Self, I> {
+    fn intersection<I: SkipAheadIterator<T>>(self, iter: I) -> Intersection<T, Self, I> {
         Intersection::new(self, iter)
     }
 
     /// Iterate over the set difference of this Iterator and the given Iterator
     /// in the order defined by their elements Ord trait implementation.
-    fn difference<I: SkipAheadIterator<'a, T>>(self, iter: I) -> Difference<'a, T, Self, I> {
+    fn difference<I: SkipAheadIterator<T>>(self, iter: I) -> Difference<T, Self, I> {
         Difference::new(self, iter)
     }
 
     /// Iterate over the set symmetric difference of this Iterator and the given Iterator
     /// in the order defined by their elements Ord trait implementation.
-    fn symmetric_difference<I: SkipAheadIterator<'a, T>>(
-        self,
-        iter: I,
-    ) -> SymmetricDifference<'a, T, Self, I> {
+    fn symmetric_difference<I>(self, iter: I) -> SymmetricDifference<T, Self, I>
+    where
+        I: SkipAheadIterator<T, Item = Self::Item>,
+    {
         SymmetricDifference::new(self, iter)
     }
 
     /// Is the output of the given Iterator disjoint from the output of
     /// this iterator?
-    fn is_disjoint<I: SkipAheadIterator<'a, T>>(self, iter: I) -> bool {
+    fn is_disjoint<I: SkipAheadIterator<T>>(self, iter: I) -> bool {
         are_disjoint(self, iter)
     }
 
     /// Is the output of the given Iterator a proper subset of the output of
     /// this iterator?
-    fn is_proper_subset<I: SkipAheadIterator<'a, T>>(self, iter: I) -> bool {
+    fn is_proper_subset<I: SkipAheadIterator<T>>(self, iter: I) -> bool {
         a_proper_superset_b(self, iter)
     }
 
     /// Is the output of the given Iterator a proper superset of the output of
     /// this iterator?
-    fn is_proper_superset<I: SkipAheadIterator<'a, T>>(self, iter: I) -> bool {
+    fn is_proper_superset<I: SkipAheadIterator<T>>(self, iter: I) -> bool {
         a_proper_superset_b(iter, self)
     }
 
     /// Is the output of the given Iterator a subset of the output of
     /// this iterator?
-    fn is_subset<I: SkipAheadIterator<'a, T>>(self, iter: I) -> bool {
+    fn is_subset<I: SkipAheadIterator<T>>(self, iter: I) -> bool {
         a_superset_b(self, iter)
     }
 
     /// Is the output of the given Iterator a superset of the output of
     /// this iterator?
-    fn is_superset<I: SkipAheadIterator<'a, T>>(self, iter: I) -> bool {
+    fn is_superset<I: SkipAheadIterator<T>>(self, iter: I) -> bool {
         a_superset_b(iter, self)
     }
 }
 
+// INTEGER RANGES
+
+// Binary search an integer range for the first value for which `before` is false. The
+// target may be a borrowed form of the integer type so it can't be used directly.
+macro_rules! range_partition_point {
+    ( $range:expr, $before:expr ) => {{
+        let before = $before;
+        let (mut low, mut high) = ($range.start, $range.end);
+        while low < high {
+            // The mean rounded down without overflowing.
+            let mid = (low & high) + ((low ^ high) >> 1);
+            if before(&mid) {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        low
+    }};
+}
+
+macro_rules! impl_skip_ahead_for_range {
+    ( $( $t:ty ),* ) => {
+        $(
+            impl SkipAheadIterator<$t> for Range<$t> {
+                fn peek(&mut self) -> Option<&$t> {
+                    if self.start < self.end {
+                        Some(&self.start)
+                    } else {
+                        None
+                    }
+                }
+
+                fn advance_past<Q>(&mut self, t: &Q) -> &mut Self
+                where
+                    $t: Borrow<Q>,
+                    Q: Ord + ?Sized,
+                {
+                    self.start = range_partition_point!(self, |x: &$t| x.borrow() <= t);
+                    self
+                }
+
+                fn advance_until<Q>(&mut self, t: &Q) -> &mut Self
+                where
+                    $t: Borrow<Q>,
+                    Q: Ord + ?Sized,
+                {
+                    self.start = range_partition_point!(self, |x: &$t| x.borrow() < t);
+                    self
+                }
+            }
+
+            impl IterSetOperations<$t> for Range<$t> {}
+        )*
+    };
+}
+
+impl_skip_ahead_for_range!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
 /// The contents of the two iterators are disjoint
-pub(crate) fn are_disjoint<'a, T, L, R>(mut l_iter: L, mut r_iter: R) -> bool
+pub(crate) fn are_disjoint<T, L, R>(mut l_iter: L, mut r_iter: R) -> bool
 where
-    T: 'a + Ord,
-    L: SkipAheadIterator<'a, T>,
-    R: SkipAheadIterator<'a, T>,
+    T: Ord,
+    L: SkipAheadIterator<T>,
+    R: SkipAheadIterator<T>,
 {
     loop {
         if let Some(l_element) = l_iter.peek() {
             if let Some(r_element) = r_iter.peek() {
-                match l_element.cmp(&r_element) {
+                match l_element.cmp(r_element) {
                     Ordering::Less => {
                         l_iter.advance_until(r_element);
                     }
@@ -316,15 +536,15 @@ where
 }
 
 /// The contents of Iterator "a" are a superset of the contents of "b"
-pub(crate) fn a_superset_b<'a, T, A, B>(mut a_iter: A, mut b_iter: B) -> bool
+pub(crate) fn a_superset_b<T, A, B>(mut a_iter: A, mut b_iter: B) -> bool
 where
-    T: 'a + Ord,
-    A: SkipAheadIterator<'a, T>,
-    B: SkipAheadIterator<'a, T>,
+    T: Ord,
+    A: SkipAheadIterator<T>,
+    B: SkipAheadIterator<T>,
 {
     while let Some(b_element) = b_iter.peek() {
         if let Some(a_element) = a_iter.peek() {
-            match b_element.cmp(&a_element) {
+            match b_element.cmp(a_element) {
                 Ordering::Less => {
                     return false;
                 }
@@ -344,16 +564,16 @@ where
 }
 
 /// The contents of Iterator "a" are a proper superset of the contents of "b"
-pub(crate) fn a_proper_superset_b<'a, T, A, B>(mut a_iter: A, mut b_iter: B) -> bool
+pub(crate) fn a_proper_superset_b<T, A, B>(mut a_iter: A, mut b_iter: B) -> bool
 where
-    T: 'a + Ord,
-    A: SkipAheadIterator<'a, T>,
-    B: SkipAheadIterator<'a, T>,
+    T: Ord,
+    A: SkipAheadIterator<T>,
+    B: SkipAheadIterator<T>,
 {
     let mut result = false;
     while let Some(b_element) = b_iter.peek() {
         if let Some(a_element) = a_iter.peek() {
-            match b_element.cmp(&a_element) {
+            match b_element.cmp(a_element) {
                 Ordering::Less => {
                     return false;
                 }
@@ -374,16 +594,14 @@ where
 }
 
 macro_rules! impl_op_for_iterator {
-    ( $iterator:ident, $op:ident, $op_fn:ident, $output:ident, $doc:meta, ) => {
-        impl<'a, T, L, R, I> $op<I> for $iterator<'a, T, L, R>
+    ( $iterator:ident, $op:ident, $op_fn:ident, $output:ident, [ $( $item:tt )* ], $doc:meta, ) => {
+        impl<T, L, R, I> $op<I> for $iterator<T, L, R>
         where
             T: Ord,
-            Self: Sized,
-            L: SkipAheadIterator<'a, T>,
-            R: SkipAheadIterator<'a, T>,
-            I: SkipAheadIterator<'a, T>,
+            Self: SkipAheadIterator<T>,
+            I: SkipAheadIterator<T, $( $item )*>,
         {
-            type Output = $output<'a, T, Self, I>;
+            type Output = $output<T, Self, I>;
 
             #[$doc]
             fn $op_fn(self, other: I) -> Self::Output {
@@ -396,22 +614,17 @@ macro_rules! impl_op_for_iterator {
 macro_rules! define_set_op_iterator {
     ( $doc:meta, $iter:ident ) => {
         #[$doc]
-        pub struct $iter<'a, T, L, R>
-        where
-            T: Ord,
-            L: SkipAheadIterator<'a, T>,
-            R: SkipAheadIterator<'a, T>,
-        {
+        pub struct $iter<T, L, R> {
             l_iter: L,
             r_iter: R,
-            phantom: PhantomData<&'a T>,
+            phantom: PhantomData<T>,
         }
 
-        impl<'a, T, L, R> $iter<'a, T, L, R>
+        impl<T, L, R> $iter<T, L, R>
         where
-            T: 'a + Ord,
-            L: SkipAheadIterator<'a, T>,
-            R: SkipAheadIterator<'a, T>,
+            T: Ord,
+            L: SkipAheadIterator<T>,
+            R: SkipAheadIterator<T>,
         {
             pub(crate) fn new(l_iter: L, r_iter: R) -> Self {
                 Self {
@@ -422,35 +635,33 @@ macro_rules! define_set_op_iterator {
             }
         }
 
-        impl<'a, T, L, R> ToList<'a, T> for $iter<'a, T, L, R>
+        #[cfg(feature = "alloc")]
+        impl<'a, T, L, R> ToList<'a, T> for $iter<T, L, R>
         where
             T: 'a + Ord + Clone,
-            L: SkipAheadIterator<'a, T>,
-            R: SkipAheadIterator<'a, T>,
+            Self: SkipAheadIterator<T, Item = &'a T>,
         {
         }
 
-        impl<'a, T, L, R> ToSet<'a, T> for $iter<'a, T, L, R>
+        #[cfg(feature = "alloc")]
+        impl<'a, T, L, R> ToSet<'a, T> for $iter<T, L, R>
         where
             T: 'a + Ord + Clone,
-            L: SkipAheadIterator<'a, T>,
-            R: SkipAheadIterator<'a, T>,
+            Self: SkipAheadIterator<T, Item = &'a T>,
         {
         }
 
-        impl<'a, T, L, R> Selection<'a, T> for $iter<'a, T, L, R>
+        impl<'a, T, L, R> Selection<'a, T> for $iter<T, L, R>
         where
             T: 'a + Ord + Clone,
-            L: SkipAheadIterator<'a, T>,
-            R: SkipAheadIterator<'a, T>,
+            Self: SkipAheadIterator<T, Item = &'a T>,
         {
         }
 
-        impl<'a, T, L, R> IterSetOperations<'a, T> for $iter<'a, T, L, R>
+        impl<T, L, R> IterSetOperations<T> for $iter<T, L, R>
         where
-            T: 'a + Ord,
-            L: SkipAheadIterator<'a, T>,
-            R: SkipAheadIterator<'a, T>,
+            T: Ord,
+            Self: SkipAheadIterator<T>,
         {
         }
 
@@ -459,6 +670,7 @@ macro_rules! define_set_op_iterator {
             BitOr,
             bitor,
             Union,
+            [Item = <Self as Iterator>::Item],
             doc = "Apply the | operator to return a new ordered iterator
         over the union of this iteratro and other
         i.e. the elements that are in this iterator or in other.",
@@ -469,6 +681,7 @@ macro_rules! define_set_op_iterator {
             BitAnd,
             bitand,
             Intersection,
+            [],
             doc = "Apply the & operator to return a new ordered iterator
         over the intersection of this iterator and other i.e. the
         elements that are in both this set and in other.",
@@ -479,6 +692,7 @@ macro_rules! define_set_op_iterator {
             BitXor,
             bitxor,
             SymmetricDifference,
+            [Item = <Self as Iterator>::Item],
             doc = "Apply the ^ operator to return a new ordered iterator over
         the symmetric set difference between this iterator and other
         i.e. the elements that are in this iterator or in other but not in both.",
@@ -489,6 +703,7 @@ macro_rules! define_set_op_iterator {
             Sub,
             sub,
             Difference,
+            [],
             doc = "Apply the - operator to return a new ordered iterator over
         the set difference between this set and other i.e. the elements
         that are in this iterator but not in other.",
@@ -502,18 +717,18 @@ define_set_op_iterator!(
     Union
 );
 
-impl<'a, T, L, R> Iterator for Union<'a, T, L, R>
+impl<T, L, R> Iterator for Union<T, L, R>
 where
-    T: 'a + Ord,
-    L: SkipAheadIterator<'a, T>,
-    R: SkipAheadIterator<'a, T>,
+    T: Ord,
+    L: SkipAheadIterator<T>,
+    R: SkipAheadIterator<T, Item = L::Item>,
 {
-    type Item = &'a T;
+    type Item = L::Item;
 
     fn next(&mut self) -> Option<Self::Item> {
         if let Some(l_element) = self.l_iter.peek() {
             if let Some(r_element) = self.r_iter.peek() {
-                match l_element.cmp(&r_element) {
+                match l_element.cmp(r_element) {
                     Ordering::Less => {
                         return self.l_iter.next();
                     }
@@ -534,16 +749,16 @@ where
     }
 }
 
-impl<'a, T, L, R> SkipAheadIterator<'a, T> for Union<'a, T, L, R>
+impl<T, L, R> SkipAheadIterator<T> for Union<T, L, R>
 where
-    T: 'a + Ord,
-    L: SkipAheadIterator<'a, T>,
-    R: SkipAheadIterator<'a, T>,
+    T: Ord,
+    L: SkipAheadIterator<T>,
+    R: SkipAheadIterator<T, Item = L::Item>,
 {
-    fn peek(&mut self) -> Option<&'a T> {
+    fn peek(&mut self) -> Option<&T> {
         if let Some(l_element) = self.l_iter.peek() {
             if let Some(r_element) = self.r_iter.peek() {
-                match l_element.cmp(&r_element) {
+                match l_element.cmp(r_element) {
                     Ordering::Less | Ordering::Equal => {
                         return Some(l_element);
                     }
@@ -559,13 +774,21 @@ where
         }
     }
 
-    fn advance_past(&mut self, t: &T) -> &mut Self {
+    fn advance_past<Q>(&mut self, t: &Q) -> &mut Self
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
         self.l_iter.advance_past(t);
         self.r_iter.advance_past(t);
         self
     }
 
-    fn advance_until(&mut self, t: &T) -> &mut Self {
+    fn advance_until<Q>(&mut self, t: &Q) -> &mut Self
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
         self.l_iter.advance_until(t);
         self.r_iter.advance_until(t);
         self
@@ -578,24 +801,24 @@ define_set_op_iterator!(
     Intersection
 );
 
-impl<'a, T, L, R> Iterator for Intersection<'a, T, L, R>
+impl<T, L, R> Iterator for Intersection<T, L, R>
 where
-    T: 'a + Ord,
-    L: SkipAheadIterator<'a, T>,
-    R: SkipAheadIterator<'a, T>,
+    T: Ord,
+    L: SkipAheadIterator<T>,
+    R: SkipAheadIterator<T>,
 {
-    type Item = &'a T;
+    type Item = L::Item;
 
     fn next(&mut self) -> Option<Self::Item> {
         loop {
             if let Some(l_element) = self.l_iter.peek() {
                 if let Some(r_element) = self.r_iter.peek() {
-                    match l_element.cmp(&r_element) {
+                    match l_element.cmp(r_element) {
                         Ordering::Less => {
-                            self.l_iter.advance_until(&r_element);
+                            self.l_iter.advance_until(r_element);
                         }
                         Ordering::Greater => {
-                            self.r_iter.advance_until(&l_element);
+                            self.r_iter.advance_until(l_element);
                         }
                         Ordering::Equal => {
                             self.r_iter.next();
@@ -612,25 +835,30 @@ where
     }
 }
 
-impl<'a, T, L, R> SkipAheadIterator<'a, T> for Intersection<'a, T, L, R>
+// The `peek()` implementations below that may have to skip items before finding the
+// next one re-peek the settled iterator rather than returning the borrowed element as
+// the borrow checker will not allow a borrow returned from one pass of the loop to
+// coexist with the mutation in another.
+
+impl<T, L, R> SkipAheadIterator<T> for Intersection<T, L, R>
 where
-    T: 'a + Ord,
-    L: SkipAheadIterator<'a, T>,
-    R: SkipAheadIterator<'a, T>,
+    T: Ord,
+    L: SkipAheadIterator<T>,
+    R: SkipAheadIterator<T>,
 {
-    fn peek(&mut self) -> Option<&'a T> {
+    fn peek(&mut self) -> Option<&T> {
         loop {
             if let Some(l_element) = self.l_iter.peek() {
                 if let Some(r_element) = self.r_iter.peek() {
-                    match l_element.cmp(&r_element) {
+                    match l_element.cmp(r_element) {
                         Ordering::Less => {
-                            self.l_iter.advance_until(&r_element);
+                            self.l_iter.advance_until(r_element);
                         }
                         Ordering::Greater => {
-                            self.r_iter.advance_until(&l_element);
+                            self.r_iter.advance_until(l_element);
                         }
                         Ordering::Equal => {
-                            return Some(l_element);
+                            return self.l_iter.peek();
                         }
                     }
                 } else {
@@ -642,13 +870,21 @@ where
         }
     }
 
-    fn advance_past(&mut self, t: &T) -> &mut Self {
+    fn advance_past<Q>(&mut self, t: &Q) -> &mut Self
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
         self.l_iter.advance_past(t);
         self.r_iter.advance_past(t);
         self
     }
 
-    fn advance_until(&mut self, t: &T) -> &mut Self {
+    fn advance_until<Q>(&mut self, t: &Q) -> &mut Self
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
         self.l_iter.advance_until(t);
         self.r_iter.advance_until(t);
         self
@@ -661,24 +897,24 @@ Iterators whose (individual) output is ordered and contains no duplicates.",
     Difference
 );
 
-impl<'a, T, L, R> Iterator for Difference<'a, T, L, R>
+impl<T, L, R> Iterator for Difference<T, L, R>
 where
-    T: 'a + Ord,
-    L: SkipAheadIterator<'a, T>,
-    R: SkipAheadIterator<'a, T>,
+    T: Ord,
+    L: SkipAheadIterator<T>,
+    R: SkipAheadIterator<T>,
 {
-    type Item = &'a T;
+    type Item = L::Item;
 
     fn next(&mut self) -> Option<Self::Item> {
         loop {
             if let Some(l_element) = self.l_iter.peek() {
                 if let Some(r_element) = self.r_iter.peek() {
-                    match l_element.cmp(&r_element) {
+                    match l_element.cmp(r_element) {
                         Ordering::Less => {
                             return self.l_iter.next();
                         }
                         Ordering::Greater => {
-                            self.r_iter.advance_until(&l_element);
+                            self.r_iter.advance_until(l_element);
                         }
                         Ordering::Equal => {
                             self.l_iter.next();
@@ -695,22 +931,22 @@ where
     }
 }
 
-impl<'a, T, L, R> SkipAheadIterator<'a, T> for Difference<'a, T, L, R>
+impl<T, L, R> SkipAheadIterator<T> for Difference<T, L, R>
 where
-    T: 'a + Ord,
-    L: SkipAheadIterator<'a, T>,
-    R: SkipAheadIterator<'a, T>,
+    T: Ord,
+    L: SkipAheadIterator<T>,
+    R: SkipAheadIterator<T>,
 {
-    fn peek(&mut self) -> Option<&'a T> {
+    fn peek(&mut self) -> Option<&T> {
         loop {
             if let Some(l_element) = self.l_iter.peek() {
                 if let Some(r_element) = self.r_iter.peek() {
-                    match l_element.cmp(&r_element) {
+                    match l_element.cmp(r_element) {
                         Ordering::Less => {
-                            return Some(l_element);
+                            return self.l_iter.peek();
                         }
                         Ordering::Greater => {
-                            self.r_iter.advance_until(&l_element);
+                            self.r_iter.advance_until(l_element);
                         }
                         Ordering::Equal => {
                             self.l_iter.next();
@@ -718,7 +954,7 @@ where
                         }
                     }
                 } else {
-                    return Some(l_element);
+                    return self.l_iter.peek();
                 }
             } else {
                 return None;
@@ -726,13 +962,21 @@ where
         }
     }
 
-    fn advance_past(&mut self, t: &T) -> &mut Self {
+    fn advance_past<Q>(&mut self, t: &Q) -> &mut Self
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
         self.l_iter.advance_past(t);
         self.r_iter.advance_past(t);
         self
     }
 
-    fn advance_until(&mut self, t: &T) -> &mut Self {
+    fn advance_until<Q>(&mut self, t: &Q) -> &mut Self
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
         self.l_iter.advance_until(t);
         self.r_iter.advance_until(t);
         self
@@ -745,19 +989,19 @@ Iterators whose (individual) output is ordered and contains no duplicates.",
     SymmetricDifference
 );
 
-impl<'a, T, L, R> Iterator for SymmetricDifference<'a, T, L, R>
+impl<T, L, R> Iterator for SymmetricDifference<T, L, R>
 where
-    T: 'a + Ord,
-    L: SkipAheadIterator<'a, T>,
-    R: SkipAheadIterator<'a, T>,
+    T: Ord,
+    L: SkipAheadIterator<T>,
+    R: SkipAheadIterator<T, Item = L::Item>,
 {
-    type Item = &'a T;
+    type Item = L::Item;
 
     fn next(&mut self) -> Option<Self::Item> {
         loop {
             if let Some(l_element) = self.l_iter.peek() {
                 if let Some(r_element) = self.r_iter.peek() {
-                    match l_element.cmp(&r_element) {
+                    match l_element.cmp(r_element) {
                         Ordering::Less => {
                             return self.l_iter.next();
                         }
@@ -779,22 +1023,22 @@ where
     }
 }
 
-impl<'a, T, L, R> SkipAheadIterator<'a, T> for SymmetricDifference<'a, T, L, R>
+impl<T, L, R> SkipAheadIterator<T> for SymmetricDifference<T, L, R>
 where
-    T: 'a + Ord,
-    L: SkipAheadIterator<'a, T>,
-    R: SkipAheadIterator<'a, T>,
+    T: Ord,
+    L: SkipAheadIterator<T>,
+    R: SkipAheadIterator<T, Item = L::Item>,
 {
-    fn peek(&mut self) -> Option<&'a T> {
+    fn peek(&mut self) -> Option<&T> {
         loop {
             if let Some(l_element) = self.l_iter.peek() {
                 if let Some(r_element) = self.r_iter.peek() {
-                    match l_element.cmp(&r_element) {
+                    match l_element.cmp(r_element) {
                         Ordering::Less => {
-                            return Some(l_element);
+                            return self.l_iter.peek();
                         }
                         Ordering::Greater => {
-                            return Some(r_element);
+                            return self.r_iter.peek();
                         }
                         Ordering::Equal => {
                             self.l_iter.next();
@@ -802,7 +1046,7 @@ where
                         }
                     }
                 } else {
-                    return Some(l_element);
+                    return self.l_iter.peek();
                 }
             } else {
                 return self.r_iter.peek();
@@ -810,20 +1054,28 @@ where
         }
     }
 
-    fn advance_past(&mut self, t: &T) -> &mut Self {
+    fn advance_past<Q>(&mut self, t: &Q) -> &mut Self
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
         self.l_iter.advance_past(t);
         self.r_iter.advance_past(t);
         self
     }
 
-    fn advance_until(&mut self, t: &T) -> &mut Self {
+    fn advance_until<Q>(&mut self, t: &Q) -> &mut Self
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
         self.l_iter.advance_until(t);
         self.r_iter.advance_until(t);
         self
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "alloc"))]
 mod tests {
     use super::*;
 
@@ -832,7 +1084,7 @@ mod tests {
     fn output_is_ordered_nodups<'a, T, I>(iter: &mut I) -> bool
     where
         T: 'a + Ord,
-        I: SkipAheadIterator<'a, T>,
+        I: SkipAheadIterator<T, Item = &'a T>,
     {
         let mut o_previous = iter.next();
         while let Some(previous) = o_previous {
@@ -1158,4 +1410,139 @@ mod tests {
             &["e", "h", "k", "l"]
         );
     }
+
+    // An owned item `SkipAheadIterator` over ascending ids parsed from lines of text
+    // standing in for a file reader.
+    struct Ids<I: Iterator<Item = u64>> {
+        iter: I,
+        next: Option<u64>,
+    }
+
+    impl<'b> Ids<core::iter::Map<core::str::Lines<'b>, fn(&str) -> u64>> {
+        fn new(text: &'b str) -> Self {
+            let mut iter = text
+                .lines()
+                .map((|line| line.parse().unwrap()) as fn(&str) -> u64);
+            let next = iter.next();
+            Self { iter, next }
+        }
+    }
+
+    impl<I: Iterator<Item = u64>> Iterator for Ids<I> {
+        type Item = u64;
+
+        fn next(&mut self) -> Option<u64> {
+            let next = self.next.take();
+            self.next = self.iter.next();
+            next
+        }
+    }
+
+    impl<I: Iterator<Item = u64>> SkipAheadIterator<u64> for Ids<I> {
+        fn peek(&mut self) -> Option<&u64> {
+            self.next.as_ref()
+        }
+
+        fn advance_past<Q>(&mut self, t: &Q) -> &mut Self
+        where
+            u64: Borrow<Q>,
+            Q: Ord + ?Sized,
+        {
+            while matches!(self.next, Some(id) if id.borrow() <= t) {
+                self.next();
+            }
+            self
+        }
+
+        fn advance_until<Q>(&mut self, t: &Q) -> &mut Self
+        where
+            u64: Borrow<Q>,
+            Q: Ord + ?Sized,
+        {
+            while matches!(self.next, Some(id) if id.borrow() < t) {
+                self.next();
+            }
+            self
+        }
+    }
+
+    impl<I: Iterator<Item = u64>> IterSetOperations<u64> for Ids<I> {}
+
+    static IDS: &str = "3\n7\n8\n15\n21\n40";
+
+    #[test]
+    fn owned_item_set_operations() {
+        let ids: &[u64] = &[1, 7, 15, 16, 40, 41];
+        let owned: Vec<u64> = Ids::new(IDS).intersection(SetIter::new(ids)).collect();
+        assert_eq!(owned, vec![7, 15, 40]);
+        let borrowed: Vec<&u64> = SetIter::new(ids).intersection(Ids::new(IDS)).collect();
+        assert_eq!(borrowed, vec![&7, &15, &40]);
+        let owned: Vec<u64> = Ids::new(IDS).difference(SetIter::new(ids)).collect();
+        assert_eq!(owned, vec![3, 8, 21]);
+        let borrowed: Vec<u64> = (SetIter::new(ids) - Ids::new(IDS)).cloned().collect();
+        assert_eq!(borrowed, vec![1, 16, 41]);
+        let owned: Vec<u64> = Ids::new(IDS).union(5..9).collect();
+        assert_eq!(owned, vec![3, 5, 6, 7, 8, 15, 21, 40]);
+        let owned: Vec<u64> = Ids::new(IDS).symmetric_difference(5..9).collect();
+        assert_eq!(owned, vec![3, 5, 6, 15, 21, 40]);
+        assert!(SetIter::new(&[7, 15]).is_superset(Ids::new(IDS)));
+        assert!(Ids::new(IDS).is_disjoint(SetIter::new(&[4, 9, 22])));
+        let mut iter = SetIter::new(ids) & Ids::new(IDS);
+        assert_eq!(iter.advance_past(&7).peek(), Some(&15));
+        assert_eq!(iter.advance_until(&40).next(), Some(&40));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn range_set_operations() {
+        let union: Vec<u32> = ((0..4).union(2..6) | (10..12)).collect();
+        assert_eq!(union, vec![0, 1, 2, 3, 4, 5, 10, 11]);
+        let list: &[u32] = &[1, 3, 5, 7, 9];
+        assert_eq!(
+            (2..8)
+                .intersection(SetIter::new(list))
+                .collect::<Vec<u32>>(),
+            vec![3, 5, 7]
+        );
+        assert_eq!((SetIter::new(list) - (2..8)).to_list(), vec![1, 9]);
+        let mut range = 0u8..u8::MAX;
+        assert_eq!(range.advance_past(&3).peek(), Some(&4));
+        assert_eq!(range.advance_until(&2).peek(), Some(&4));
+        assert_eq!(range.advance_past(&u8::MAX).peek(), None);
+        assert!((0..10).is_subset(3..7));
+        assert!(!(0..10).is_disjoint(9..12));
+    }
+
+    #[test]
+    fn selector_set_operations() {
+        let list: &[u32] = &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12];
+        let odds: &[u32] = &[1, 3, 5, 7, 9, 11];
+        let even = SetIter::new(list).select(|x| **x % 2 == 0);
+        assert_eq!(
+            (even & SetIter::new(&[2, 3, 4, 9, 10])).to_list(),
+            vec![2, 4, 10]
+        );
+        let even = SetIter::new(list).select(|x| **x % 2 == 0);
+        assert_eq!((even | SetIter::new(odds)).to_list(), list.to_vec());
+        let small = SetIter::new(list).select(|x| **x < 6);
+        assert_eq!((small - SetIter::new(odds)).to_list(), vec![2, 4]);
+        let small = SetIter::new(list).select(|x| **x < 6);
+        assert_eq!((small ^ SetIter::new(odds)).to_list(), vec![2, 4, 7, 9, 11]);
+        let fizz = SetIter::new(list).select(|x| **x % 3 == 0);
+        assert!(fizz.is_disjoint(SetIter::new(&[1, 2, 4, 5, 7])));
+        let evens = SetIter::new(list).select(|x| **x % 2 == 0);
+        assert_eq!(
+            SetIter::new(odds)
+                .union(evens.select(|x| **x > 8))
+                .to_list(),
+            vec![1, 3, 5, 7, 9, 10, 11, 12]
+        );
+        let mut fizz = SetIter::new(list).select(|x| **x % 3 == 0);
+        assert_eq!(fizz.peek(), Some(&3));
+        assert_eq!(fizz.advance_until(&3).peek(), Some(&3));
+        assert_eq!(fizz.advance_past(&3).peek(), Some(&6));
+        assert_eq!(fizz.advance_until(&7).next(), Some(&9));
+        assert_eq!(fizz.advance_past(&10).peek(), Some(&12));
+        assert_eq!(fizz.advance_past(&12).next(), None);
+    }
 }
@@ -0,0 +1,127 @@
+use crate::storage::{Heap, Storage, StorageKind};
+use crate::OrderedSet;
+
+/// An occupied `Entry` holding the set's stored item that is equal to the entry's value.
+pub struct OccupiedEntry<'a, T: Ord, S: StorageKind = Heap> {
+    index: usize,
+    set: &'a mut OrderedSet<T, S>,
+}
+
+impl<'a, T: Ord, S: StorageKind> OccupiedEntry<'a, T, S> {
+    /// Returns a reference to the item stored in the set.
+    pub fn get(&self) -> &T {
+        &self.set.members.as_slice()[self.index]
+    }
+
+    /// Removes the stored item from the set and returns it.
+    pub fn remove(self) -> T {
+        self.set.members.remove(self.index)
+    }
+}
+
+/// A vacant `Entry` holding a value that is not in the set.
+pub struct VacantEntry<'a, T: Ord, S: StorageKind = Heap> {
+    value: T,
+    index: usize,
+    set: &'a mut OrderedSet<T, S>,
+}
+
+impl<'a, T: Ord, S: StorageKind> VacantEntry<'a, T, S> {
+    /// Returns a reference to the value that would be inserted.
+    pub fn get(&self) -> &T {
+        &self.value
+    }
+
+    /// Takes ownership of the value without inserting it.
+    pub fn into_value(self) -> T {
+        self.value
+    }
+
+    /// Inserts the value in the set.
+    pub fn insert(self) {
+        self.set.members.insert(self.index, self.value);
+    }
+}
+
+/// A view into a single entry in an `OrderedSet` which may be vacant or occupied.
+pub enum Entry<'a, T: Ord, S: StorageKind = Heap> {
+    Occupied(OccupiedEntry<'a, T, S>),
+    Vacant(VacantEntry<'a, T, S>),
+}
+
+impl<'a, T: Ord, S: StorageKind> Entry<'a, T, S> {
+    /// Returns a reference to the stored item if the entry is occupied and otherwise
+    /// to the entry's value.
+    pub fn get(&self) -> &T {
+        match self {
+            Entry::Occupied(entry) => entry.get(),
+            Entry::Vacant(entry) => entry.get(),
+        }
+    }
+
+    /// Ensures the entry's value is in the set by inserting it if the entry is vacant.
+    pub fn or_insert(self) {
+        if let Entry::Vacant(entry) = self {
+            entry.insert();
+        }
+    }
+
+    /// Ensures the entry's value is in the set and returns an `OccupiedEntry` for it.
+    pub fn insert(self) -> OccupiedEntry<'a, T, S> {
+        match self {
+            Entry::Occupied(entry) => entry,
+            Entry::Vacant(entry) => {
+                let VacantEntry { value, index, set } = entry;
+                set.members.insert(index, value);
+                OccupiedEntry { index, set }
+            }
+        }
+    }
+}
+
+impl<T: Ord, S: StorageKind> OrderedSet<T, S> {
+    /// Gets the `Entry` in the `OrderedSet` for `value` for in-place manipulation.
+    pub fn entry(&mut self, value: T) -> Entry<'_, T, S> {
+        match self.members.as_slice().binary_search(&value) {
+            Ok(index) => Entry::Occupied(OccupiedEntry { index, set: self }),
+            Err(index) => Entry::Vacant(VacantEntry {
+                value,
+                index,
+                set: self,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ordered_set::ord_set_iterators::ToList;
+
+    #[test]
+    fn set_entry_insert_and_remove() {
+        let mut set: OrderedSet<u32> = vec![1, 5, 9].into();
+        match set.entry(3) {
+            Entry::Vacant(entry) => {
+                assert_eq!(entry.get(), &3);
+                entry.insert();
+            }
+            Entry::Occupied(_) => panic!("3 is not in the set"),
+        }
+        assert_eq!(set.iter().to_list(), vec![1, 3, 5, 9]);
+        match set.entry(5) {
+            Entry::Occupied(entry) => assert_eq!(entry.remove(), 5),
+            Entry::Vacant(_) => panic!("5 is in the set"),
+        }
+        assert_eq!(set.iter().to_list(), vec![1, 3, 9]);
+        set.entry(7).or_insert();
+        set.entry(7).or_insert();
+        assert_eq!(set.iter().to_list(), vec![1, 3, 7, 9]);
+        assert_eq!(set.entry(0).insert().get(), &0);
+        assert_eq!(set.len(), 5);
+        if let Entry::Vacant(entry) = set.entry(4) {
+            assert_eq!(entry.into_value(), 4);
+        }
+        assert!(!set.contains(&4));
+    }
+}
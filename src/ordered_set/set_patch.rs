@@ -0,0 +1,138 @@
+use alloc::vec::Vec;
+
+use crate::ordered_set::ord_set_iterators::ToSet;
+use crate::OrderedSet;
+
+/// A compact description of the changes required to transform one `OrderedSet` into another
+/// consisting of (sorted) lists of the elements to be added and removed.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct SetPatch<T: Ord> {
+    added: OrderedSet<T>,
+    removed: OrderedSet<T>,
+}
+
+impl<T: Ord> SetPatch<T> {
+    /// Create a `SetPatch` from its component sets. Elements in both `added` and `removed`
+    /// will be present after the patch is applied.
+    pub fn from_parts(added: OrderedSet<T>, removed: OrderedSet<T>) -> Self {
+        Self { added, removed }
+    }
+
+    /// Return the patch's component sets as an `(added, removed)` pair.
+    pub fn into_parts(self) -> (OrderedSet<T>, OrderedSet<T>) {
+        (self.added, self.removed)
+    }
+
+    /// Return the set of elements that this patch adds.
+    pub fn added(&self) -> &OrderedSet<T> {
+        &self.added
+    }
+
+    /// Return the set of elements that this patch removes.
+    pub fn removed(&self) -> &OrderedSet<T> {
+        &self.removed
+    }
+
+    /// Return `true` if applying this patch would make no changes.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+
+    /// Return the patch that undoes the effect of this patch.
+    pub fn invert(self) -> Self {
+        Self {
+            added: self.removed,
+            removed: self.added,
+        }
+    }
+
+    /// Apply this patch to `set` in a single ordered pass. The patch is consumed so that
+    /// the elements it adds are moved, rather than cloned, into `set`.
+    pub fn apply(self, set: &mut OrderedSet<T>) {
+        let mut members: Vec<T> = Vec::with_capacity(set.len() + self.added.len());
+        let mut removed = self.removed.members.into_iter().peekable();
+        let mut added = self.added.members.into_iter().peekable();
+        for member in core::mem::take(&mut set.members) {
+            while let Some(item) = added.next_if(|item| *item < member) {
+                members.push(item);
+            }
+            while removed.next_if(|item| *item < member).is_some() {}
+            let is_removed = removed.next_if(|item| *item == member).is_some();
+            let is_added = added.next_if(|item| *item == member).is_some();
+            if is_added || !is_removed {
+                members.push(member);
+            }
+        }
+        members.extend(added);
+        set.members = members;
+    }
+}
+
+impl<T: Ord + Clone> OrderedSet<T> {
+    /// Return the `SetPatch` that transforms this set into `new`.
+    pub fn diff(&self, new: &Self) -> SetPatch<T> {
+        SetPatch {
+            added: new.difference(self).to_set(),
+            removed: self.difference(new).to_set(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ordered_set::ord_set_iterators::ToList;
+
+    #[test]
+    fn set_diff_and_apply() {
+        let old: OrderedSet<&str> = vec!["a", "c", "e", "g", "i"].into();
+        let new: OrderedSet<&str> = vec!["b", "c", "d", "g", "j"].into();
+        let patch = old.diff(&new);
+        assert_eq!(patch.added().iter().to_list(), vec!["b", "d", "j"]);
+        assert_eq!(patch.removed().iter().to_list(), vec!["a", "e", "i"]);
+        let mut set = old.clone();
+        patch.apply(&mut set);
+        assert!(set.is_valid());
+        assert_eq!(set, new);
+        assert!(old.diff(&old).is_empty());
+    }
+
+    #[test]
+    fn set_patch_invert() {
+        let old: OrderedSet<u32> = vec![1, 3, 5, 7].into();
+        let new: OrderedSet<u32> = vec![2, 3, 4, 7, 8].into();
+        let patch = old.diff(&new);
+        let mut set = old.clone();
+        patch.clone().apply(&mut set);
+        assert_eq!(set, new);
+        patch.invert().apply(&mut set);
+        assert!(set.is_valid());
+        assert_eq!(set, old);
+    }
+
+    #[test]
+    fn set_patch_from_parts() {
+        let patch = SetPatch::from_parts(vec![4, 9].into(), vec![1, 4, 6].into());
+        let mut set: OrderedSet<u32> = vec![1, 2, 3, 6].into();
+        patch.clone().apply(&mut set);
+        assert!(set.is_valid());
+        assert_eq!(set.iter().to_list(), vec![2, 3, 4, 9]);
+        let (added, removed) = patch.into_parts();
+        assert_eq!(added.len(), 2);
+        assert_eq!(removed.len(), 3);
+    }
+
+    #[test]
+    fn set_patch_apply_moves_elements() {
+        #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+        struct NoClone(u32);
+        let set_of = |items: &[u32]| -> OrderedSet<NoClone> {
+            items.iter().map(|&item| NoClone(item)).collect()
+        };
+        let patch = SetPatch::from_parts(set_of(&[0, 4, 9]), set_of(&[1, 4, 6, 9, 10]));
+        let mut set = set_of(&[1, 2, 3, 4, 6, 10]);
+        patch.apply(&mut set);
+        assert!(set.is_valid());
+        assert_eq!(set, set_of(&[0, 2, 3, 4, 9]));
+    }
+}
@@ -0,0 +1,256 @@
+//! Parallel iteration, construction and set algebra for `OrderedSet` and `OrderedMap`
+//! using `rayon`. Available with the `rayon` feature.
+//!
+//! The contiguous sorted storage splits trivially and set operations are parallelised
+//! by splitting both inputs at matching pivot items and processing the pieces
+//! concurrently.
+
+use core::borrow::Borrow;
+use core::ops::{Bound, RangeBounds};
+
+use rayon::iter::{
+    FromParallelIterator, IndexedParallelIterator, IntoParallelIterator, IntoParallelRefIterator,
+    IntoParallelRefMutIterator, ParallelIterator, Zip,
+};
+use rayon::slice::{Iter, IterMut, ParallelSliceMut};
+
+use crate::ordered_set::ord_set_iterators::{
+    Difference, Intersection, SetIter, SymmetricDifference, Union,
+};
+use crate::{OrderedMap, OrderedSet, Storage, StorageKind};
+
+// Inputs with fewer items than this (in total) are combined sequentially.
+const SEQUENTIAL_LEN: usize = 1 << 12;
+
+/// A parallel iterator over the key-value pairs of an `OrderedMap`.
+pub type ParMapIter<'a, K, V> = Zip<Iter<'a, K>, Iter<'a, V>>;
+
+/// A parallel iterator over the key-value pairs of an `OrderedMap` with mutable
+/// references to the values.
+pub type ParMapIterMut<'a, K, V> = Zip<Iter<'a, K>, IterMut<'a, V>>;
+
+impl<T: Ord + Sync, S: StorageKind> OrderedSet<T, S> {
+    /// Returns a parallel iterator over the `OrderedSet`'s elements. It is indexed so
+    /// ordered adaptors such as `collect()` preserve ascending order.
+    pub fn par_iter(&self) -> Iter<'_, T> {
+        self.members.as_slice().par_iter()
+    }
+
+    /// Returns a parallel iterator over the `OrderedSet`'s elements that fall within the
+    /// given range.
+    pub fn par_range<K, R>(&self, range: R) -> Iter<'_, T>
+    where
+        K: Ord + ?Sized,
+        R: RangeBounds<K>,
+        T: Borrow<K>,
+    {
+        let members = self.members.as_slice();
+        let (start_index, end_index) = super::range_indices(members, range);
+        members[start_index..end_index].par_iter()
+    }
+}
+
+macro_rules! define_par_set_operation {
+    ( $doc:meta, $function:ident, $iter:ident ) => {
+        impl<T: Ord + Clone + Send + Sync, S: StorageKind> OrderedSet<T, S> {
+            #[$doc]
+            pub fn $function(&self, other: &Self) -> OrderedSet<T> {
+                let chunks = par_set_operation(
+                    self.members.as_slice(),
+                    other.members.as_slice(),
+                    &|a, b| {
+                        $iter::new(SetIter::new(a), SetIter::new(b))
+                            .cloned()
+                            .collect()
+                    },
+                );
+                OrderedSet::from_storage(concat(chunks))
+            }
+        }
+    };
+}
+
+define_par_set_operation!(
+    doc = "Return the set union of this set and other computed in parallel.",
+    par_union,
+    Union
+);
+
+define_par_set_operation!(
+    doc = "Return the set intersection of this set and other computed in parallel.",
+    par_intersection,
+    Intersection
+);
+
+define_par_set_operation!(
+    doc = "Return the set difference between this set and other computed in parallel.",
+    par_difference,
+    Difference
+);
+
+define_par_set_operation!(
+    doc = "Return the symmetric set difference between this set and other computed in parallel.",
+    par_symmetric_difference,
+    SymmetricDifference
+);
+
+// Split `a` and `b` at a common pivot so that every item in the left pieces is less
+// than every item in the right pieces and combine the pairs of pieces concurrently.
+// The results for the pieces are returned in ascending order.
+fn par_set_operation<T, F>(a: &[T], b: &[T], operation: &F) -> Vec<Vec<T>>
+where
+    T: Ord + Send + Sync,
+    F: Fn(&[T], &[T]) -> Vec<T> + Sync,
+{
+    if a.len() + b.len() <= SEQUENTIAL_LEN {
+        return vec![operation(a, b)];
+    }
+    let (a_index, b_index) = if a.len() >= b.len() {
+        let a_index = a.len() / 2;
+        (
+            a_index,
+            super::lower_bound_index(b, Bound::Included(&a[a_index])),
+        )
+    } else {
+        let b_index = b.len() / 2;
+        (
+            super::lower_bound_index(a, Bound::Included(&b[b_index])),
+            b_index,
+        )
+    };
+    let (mut left, mut right) = rayon::join(
+        || par_set_operation(&a[..a_index], &b[..b_index], operation),
+        || par_set_operation(&a[a_index..], &b[b_index..], operation),
+    );
+    left.append(&mut right);
+    left
+}
+
+fn concat<T>(chunks: Vec<Vec<T>>) -> Vec<T> {
+    let mut members = Vec::with_capacity(chunks.iter().map(Vec::len).sum());
+    for mut chunk in chunks {
+        members.append(&mut chunk);
+    }
+    members
+}
+
+impl<T: Ord + Send> FromParallelIterator<T> for OrderedSet<T> {
+    /// Collect the items in parallel and sort them with a parallel sort.
+    fn from_par_iter<I: IntoParallelIterator<Item = T>>(par_iter: I) -> Self {
+        let mut members: Vec<T> = par_iter.into_par_iter().collect();
+        members.par_sort_unstable();
+        members.dedup();
+        Self::from_storage(members)
+    }
+}
+
+impl<K: Ord + Sync, V> OrderedMap<K, V> {
+    /// Returns a parallel iterator over the `OrderedMap`'s key-value pairs. It is indexed
+    /// so ordered adaptors such as `collect()` preserve the ascending order of the keys.
+    pub fn par_iter(&self) -> ParMapIter<'_, K, V>
+    where
+        V: Sync,
+    {
+        self.keys.par_iter().zip(self.values.par_iter())
+    }
+
+    /// Returns a parallel iterator over the `OrderedMap`'s key-value pairs with mutable
+    /// references to the values.
+    pub fn par_iter_mut(&mut self) -> ParMapIterMut<'_, K, V>
+    where
+        V: Send,
+    {
+        self.keys.par_iter().zip(self.values.par_iter_mut())
+    }
+
+    /// Returns a parallel iterator over mutable references to the `OrderedMap`'s values.
+    pub fn par_values_mut(&mut self) -> IterMut<'_, V>
+    where
+        V: Send,
+    {
+        self.values.par_iter_mut()
+    }
+
+    /// Returns a parallel iterator over the `OrderedMap`'s key-value pairs whose key falls
+    /// within the specified range.
+    pub fn par_range<Q, R>(&self, range: R) -> ParMapIter<'_, K, V>
+    where
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+        K: Borrow<Q>,
+        V: Sync,
+    {
+        let (start_index, end_index) = super::range_indices(&self.keys, range);
+        self.keys[start_index..end_index]
+            .par_iter()
+            .zip(self.values[start_index..end_index].par_iter())
+    }
+}
+
+impl<K: Ord + Send, V: Send> FromParallelIterator<(K, V)> for OrderedMap<K, V> {
+    /// Collect the pairs in parallel and sort them with a parallel sort. If duplicate
+    /// keys are present the last value for the key in the iterator's order is used.
+    fn from_par_iter<I: IntoParallelIterator<Item = (K, V)>>(par_iter: I) -> Self {
+        let mut pairs: Vec<(K, V)> = par_iter.into_par_iter().collect();
+        pairs.par_sort_by(|a, b| a.0.cmp(&b.0));
+        let mut keys: Vec<K> = Vec::with_capacity(pairs.len());
+        let mut values: Vec<V> = Vec::with_capacity(pairs.len());
+        for (key, value) in pairs {
+            if keys.last() == Some(&key) {
+                *values.last_mut().unwrap() = value;
+            } else {
+                keys.push(key);
+                values.push(value);
+            }
+        }
+        Self { keys, values }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ordered_set::ord_set_iterators::ToList;
+    use crate::test_helpers::prelude::*;
+
+    fn set_of_multiples(factor: u64, len: u64) -> OrderedSet<u64> {
+        (0..len).into_par_iter().map(|i| i * factor).collect()
+    }
+
+    #[test]
+    fn par_set_operations_match_sequential() {
+        let set1 = set_of_multiples(2, 50_000);
+        let set2 = set_of_multiples(3, 40_000);
+        assert!(set1.is_valid() && set2.is_valid());
+        assert_eq!(set1.par_union(&set2), &set1 | &set2);
+        assert_eq!(set1.par_intersection(&set2), &set1 & &set2);
+        assert_eq!(set1.par_difference(&set2), &set1 - &set2);
+        assert_eq!(set2.par_difference(&set1), &set2 - &set1);
+        assert_eq!(set1.par_symmetric_difference(&set2), &set1 ^ &set2);
+        let empty = OrderedSet::<u64>::new();
+        assert_eq!(set1.par_union(&empty), set1);
+        assert!(empty.par_intersection(&set2).is_empty());
+    }
+
+    #[test]
+    fn par_iterators() {
+        let set: OrderedSet<u32> = vec![5, 3, 9, 1, 3, 7].into_par_iter().collect();
+        assert_eq!(set.iter().to_list(), vec![1, 3, 5, 7, 9]);
+        let doubled: Vec<u32> = set.par_iter().map(|x| x * 2).collect();
+        assert_eq!(doubled, vec![2, 6, 10, 14, 18]);
+        assert_eq!(set.par_range(3..8).sum::<u32>(), 15);
+
+        let mut map: OrderedMap<u32, u32> = vec![(3, 30), (1, 10), (2, 20), (1, 11)]
+            .into_par_iter()
+            .collect();
+        assert!(map.is_valid());
+        assert_eq!(map.get(&1), Some(&11));
+        map.par_values_mut().for_each(|value| *value += 1);
+        map.par_iter_mut()
+            .filter(|(key, _)| **key > 1)
+            .for_each(|(_, value)| *value *= 2);
+        let pairs: Vec<(u32, u32)> = map.par_iter().map(|(k, v)| (*k, *v)).collect();
+        assert_eq!(pairs, vec![(1, 12), (2, 42), (3, 62)]);
+        assert_eq!(map.par_range(2..).count(), 2);
+    }
+}
@@ -0,0 +1,681 @@
+//! Maps whose contents are stored in a sequence of sorted, reference counted chunks so
+//! that cloning is cheap and modifications only copy the chunk that they touch.
+//! Useful for those situations where read only snapshots of a large map need to be
+//! shared after every (small) update.
+
+use core::borrow::Borrow;
+use core::iter::FromIterator;
+use core::ops::{BitOr, Bound, RangeBounds};
+
+use alloc::sync::Arc;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::ordered_map::ord_map_iterators::{
+    MapIter, MapIterFilter, MapIterMerge, MapMergeIter, SkipAheadMapIterator, ToMap,
+};
+use crate::ordered_set::ord_set_iterators::{
+    IterSetOperations, Selection, SkipAheadIterator, ToList, ToSet,
+};
+use crate::OrderedMap;
+
+// Chunks are split in two when they grow beyond this size.
+const MAX_CHUNK_LEN: usize = 128;
+// Chunks are merged with a neighbour when they shrink below this size.
+const MIN_CHUNK_LEN: usize = MAX_CHUNK_LEN / 4;
+
+#[derive(Clone)]
+struct Chunk<K, V> {
+    keys: Vec<K>,
+    values: Vec<V>,
+}
+
+impl<K, V> Chunk<K, V> {
+    fn last_key(&self) -> &K {
+        // chunks are never empty
+        &self.keys[self.keys.len() - 1]
+    }
+}
+
+/// A map of keys of type K to values of type V ordered according to K's Ord implementation
+/// with O(1) `clone()` and copy on write of (only) the modified chunk.
+pub struct PersistentOrderedMap<K: Ord, V> {
+    chunks: Arc<Vec<Arc<Chunk<K, V>>>>,
+    len: usize,
+}
+
+impl<K: Ord, V> Clone for PersistentOrderedMap<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            chunks: Arc::clone(&self.chunks),
+            len: self.len,
+        }
+    }
+}
+
+impl<K: Ord, V> Default for PersistentOrderedMap<K, V> {
+    fn default() -> Self {
+        Self {
+            chunks: Arc::new(vec![]),
+            len: 0,
+        }
+    }
+}
+
+impl<K: Ord, V> PersistentOrderedMap<K, V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Return true if keys are sorted and contain no duplicate keys, no chunk is
+    // empty and the recorded length is correct.
+    #[cfg(test)]
+    pub(crate) fn is_valid(&self) -> bool {
+        let mut len = 0;
+        for (i, chunk) in self.chunks.iter().enumerate() {
+            if chunk.keys.is_empty() || chunk.keys.len() != chunk.values.len() {
+                return false;
+            }
+            if i > 0 && self.chunks[i - 1].last_key() >= &chunk.keys[0] {
+                return false;
+            }
+            for j in 1..chunk.keys.len() {
+                if chunk.keys[j - 1] >= chunk.keys[j] {
+                    return false;
+                }
+            }
+            len += chunk.keys.len();
+        }
+        len == self.len
+    }
+
+    /// Return the number of items in this map.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Removes all key-value pairs from the `PersistentOrderedMap`.
+    pub fn clear(&mut self) {
+        self.chunks = Arc::new(vec![]);
+        self.len = 0;
+    }
+
+    // Return the index of the chunk that would contain `key` if it were present.
+    fn chunk_index<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        if self.chunks.is_empty() {
+            None
+        } else {
+            let index = self
+                .chunks
+                .partition_point(|chunk| chunk.last_key().borrow() < key);
+            Some(index.min(self.chunks.len() - 1))
+        }
+    }
+
+    // Return the (chunk, offset) position of the first key that is greater than (or equal
+    // to if `inclusive` is true) `key`.
+    fn position<Q>(&self, key: &Q, inclusive: bool) -> (usize, usize)
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let before = |k: &K| {
+            if inclusive {
+                k.borrow() < key
+            } else {
+                k.borrow() <= key
+            }
+        };
+        let chunk_index = self
+            .chunks
+            .partition_point(|chunk| before(chunk.last_key()));
+        if let Some(chunk) = self.chunks.get(chunk_index) {
+            (chunk_index, chunk.keys.partition_point(|k| before(k)))
+        } else {
+            (chunk_index, 0)
+        }
+    }
+
+    /// Returns `true` if there is an entry for `key` in the `PersistentOrderedMap` and `false` otherwise.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.get(key).is_some()
+    }
+
+    /// Returns an immutable reference to the value in the `PersistentOrderedMap` associated
+    /// with `key` if it exists and `None` otherwise.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let chunk = &self.chunks[self.chunk_index(key)?];
+        match chunk.keys.binary_search_by_key(&key, |x| x.borrow()) {
+            Ok(index) => Some(&chunk.values[index]),
+            Err(_) => None,
+        }
+    }
+
+    /// Returns an iterator visiting all key-value pairs in ascending order of their keys.
+    /// The iterator item type is `(&'a K, &'a V)`.
+    pub fn iter(&self) -> PersistentMapIter<'_, K, V> {
+        let end_offset = self.chunks.last().map_or(0, |chunk| chunk.keys.len());
+        PersistentMapIter::new(&self.chunks, 0, end_offset)
+    }
+
+    /// Returns an iterator visiting all key-value pairs whose key falls within the specified
+    /// range in ascending order of their keys.
+    /// The iterator item type is `(&'a K, &'a V)`.
+    pub fn range<Q, R>(&self, range: R) -> PersistentMapIter<'_, K, V>
+    where
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+        K: Borrow<Q>,
+    {
+        crate::check_range_bounds(&range);
+        let (start_chunk, start_offset) = match range.start_bound() {
+            Bound::Unbounded => (0, 0),
+            Bound::Included(key) => self.position(key, true),
+            Bound::Excluded(key) => self.position(key, false),
+        };
+        let (end_chunk, end_offset) = match range.end_bound() {
+            Bound::Unbounded => (self.chunks.len(), 0),
+            Bound::Included(key) => self.position(key, false),
+            Bound::Excluded(key) => self.position(key, true),
+        };
+        if (start_chunk, start_offset) >= (end_chunk, end_offset) {
+            PersistentMapIter::new(&[], 0, 0)
+        } else if end_offset == 0 {
+            let chunks = &self.chunks[start_chunk..end_chunk];
+            let end_offset = chunks.last().map_or(0, |chunk| chunk.keys.len());
+            PersistentMapIter::new(chunks, start_offset, end_offset)
+        } else {
+            PersistentMapIter::new(
+                &self.chunks[start_chunk..=end_chunk],
+                start_offset,
+                end_offset,
+            )
+        }
+    }
+
+    /// Returns a `SkipAheadIterator` visiting all keys in the `PersistentOrderedMap` in
+    /// ascending order.
+    pub fn keys(&self) -> PersistentKeyIter<'_, K, V> {
+        PersistentKeyIter { iter: self.iter() }
+    }
+
+    /// Returns an iterator visiting all values in the `PersistentOrderedMap` in ascending
+    /// order of their keys.
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.iter().map(|(_, value)| value)
+    }
+}
+
+impl<K: Ord + Clone, V: Clone> PersistentOrderedMap<K, V> {
+    /// Returns an mutable reference to the value in the `PersistentOrderedMap` associated
+    /// with `key` if it exists and `None` otherwise. The chunk containing the value is copied
+    /// if it is shared with another map.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let chunk_index = self.chunk_index(key)?;
+        let index = self.chunks[chunk_index]
+            .keys
+            .binary_search_by_key(&key, |x| x.borrow())
+            .ok()?;
+        let chunk = Arc::make_mut(&mut Arc::make_mut(&mut self.chunks)[chunk_index]);
+        Some(&mut chunk.values[index])
+    }
+
+    /// Inserts a key-value (`key`, `value`) pair into the `PersistentOrderedMap` and returns
+    /// the previous value associated with `key` if it exists and `None` otherwise. Only the
+    /// chunk that receives the pair is copied if it is shared with another map.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let chunk_index = match self.chunk_index(&key) {
+            Some(chunk_index) => chunk_index,
+            None => {
+                let chunk = Chunk {
+                    keys: vec![key],
+                    values: vec![value],
+                };
+                Arc::make_mut(&mut self.chunks).push(Arc::new(chunk));
+                self.len = 1;
+                return None;
+            }
+        };
+        let chunks = Arc::make_mut(&mut self.chunks);
+        let chunk = Arc::make_mut(&mut chunks[chunk_index]);
+        match chunk.keys.binary_search(&key) {
+            Ok(index) => Some(core::mem::replace(&mut chunk.values[index], value)),
+            Err(index) => {
+                chunk.keys.insert(index, key);
+                chunk.values.insert(index, value);
+                self.len += 1;
+                if chunk.keys.len() > MAX_CHUNK_LEN {
+                    let half = chunk.keys.len() / 2;
+                    let tail = Chunk {
+                        keys: chunk.keys.split_off(half),
+                        values: chunk.values.split_off(half),
+                    };
+                    chunks.insert(chunk_index + 1, Arc::new(tail));
+                }
+                None
+            }
+        }
+    }
+
+    /// Removes `key` from the `PersistentOrderedMap` and returns the value associated with
+    /// `key` if `key` existed in it and `None` otherwise. Only the chunk that contained
+    /// the key (and the neighbour that it is merged with if it becomes too short) is
+    /// copied if it is shared with another map.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let chunk_index = self.chunk_index(key)?;
+        let index = self.chunks[chunk_index]
+            .keys
+            .binary_search_by_key(&key, |x| x.borrow())
+            .ok()?;
+        let chunks = Arc::make_mut(&mut self.chunks);
+        self.len -= 1;
+        if chunks[chunk_index].keys.len() == 1 {
+            let chunk = chunks.remove(chunk_index);
+            match Arc::try_unwrap(chunk) {
+                Ok(mut chunk) => chunk.values.pop(),
+                Err(chunk) => Some(chunk.values[0].clone()),
+            }
+        } else {
+            let chunk = Arc::make_mut(&mut chunks[chunk_index]);
+            chunk.keys.remove(index);
+            let value = chunk.values.remove(index);
+            if chunk.keys.len() < MIN_CHUNK_LEN && chunks.len() > 1 {
+                Self::merge_chunk(chunks, chunk_index);
+            }
+            Some(value)
+        }
+    }
+
+    // Merge the chunk at `index` with a neighbour, splitting the result in two if it is
+    // too long. The neighbour is only copied if it is shared with another map.
+    fn merge_chunk(chunks: &mut Vec<Arc<Chunk<K, V>>>, index: usize) {
+        let index = if index + 1 < chunks.len() {
+            index
+        } else {
+            index - 1
+        };
+        let next = chunks.remove(index + 1);
+        let next = Arc::try_unwrap(next).unwrap_or_else(|next| (*next).clone());
+        let chunk = Arc::make_mut(&mut chunks[index]);
+        chunk.keys.extend(next.keys);
+        chunk.values.extend(next.values);
+        if chunk.keys.len() > MAX_CHUNK_LEN {
+            let half = chunk.keys.len() / 2;
+            let tail = Chunk {
+                keys: chunk.keys.split_off(half),
+                values: chunk.values.split_off(half),
+            };
+            chunks.insert(index + 1, Arc::new(tail));
+        }
+    }
+
+    /// Create an `OrderedMap<K, V>` containing copies of the items in this map.
+    pub fn to_ordered_map(&self) -> OrderedMap<K, V> {
+        self.iter().to_map()
+    }
+}
+
+impl<K: Ord, V> From<OrderedMap<K, V>> for PersistentOrderedMap<K, V> {
+    fn from(map: OrderedMap<K, V>) -> Self {
+        let len = map.keys.len();
+        let mut chunks = vec![];
+        let mut keys = map.keys.into_iter();
+        let mut values = map.values.into_iter();
+        loop {
+            let chunk = Chunk {
+                keys: keys.by_ref().take(MAX_CHUNK_LEN / 2).collect(),
+                values: values.by_ref().take(MAX_CHUNK_LEN / 2).collect(),
+            };
+            if chunk.keys.is_empty() {
+                break;
+            }
+            chunks.push(Arc::new(chunk));
+        }
+        Self {
+            chunks: Arc::new(chunks),
+            len,
+        }
+    }
+}
+
+impl<K: Ord + Clone, V: Clone> FromIterator<(K, V)> for PersistentOrderedMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        OrderedMap::from_iter(iter).into()
+    }
+}
+
+impl<K: Ord + core::fmt::Debug, V: core::fmt::Debug> core::fmt::Debug
+    for PersistentOrderedMap<K, V>
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: Ord, V: PartialEq> PartialEq for PersistentOrderedMap<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<K: Ord, V: Eq> Eq for PersistentOrderedMap<K, V> {}
+
+impl<'a, K: Ord, V> IntoIterator for &'a PersistentOrderedMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = PersistentMapIter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+// PERSISTENT MAP ITERATOR
+
+/// An Iterator over the items in a `PersistentOrderedMap`
+pub struct PersistentMapIter<'a, K: Ord, V> {
+    chunks: &'a [Arc<Chunk<K, V>>],
+    start_offset: usize,
+    end_offset: usize,
+    chunk_index: usize,
+    iter: MapIter<'a, K, V>,
+}
+
+impl<'a, K: Ord, V> PersistentMapIter<'a, K, V> {
+    fn new(chunks: &'a [Arc<Chunk<K, V>>], start_offset: usize, end_offset: usize) -> Self {
+        let mut iter = Self {
+            chunks,
+            start_offset,
+            end_offset,
+            chunk_index: 0,
+            iter: MapIter::new(&[], &[]),
+        };
+        iter.iter = iter.chunk_iter(0);
+        iter
+    }
+
+    // Return a `MapIter` over the part of the indexed chunk that is within range.
+    fn chunk_iter(&self, chunk_index: usize) -> MapIter<'a, K, V> {
+        let chunks = self.chunks;
+        if let Some(chunk) = chunks.get(chunk_index) {
+            let start = if chunk_index == 0 {
+                self.start_offset
+            } else {
+                0
+            };
+            let end = if chunk_index + 1 == chunks.len() {
+                self.end_offset
+            } else {
+                chunk.keys.len()
+            };
+            MapIter::new(&chunk.keys[start..end], &chunk.values[start..end])
+        } else {
+            MapIter::new(&[], &[])
+        }
+    }
+
+    // Move to the first chunk (at or after the current chunk) whose last key is not
+    // before `key`.
+    fn skip_chunks<F: Fn(&K) -> bool>(&mut self, before: F) {
+        let skip = self.chunks[self.chunk_index.min(self.chunks.len())..]
+            .partition_point(|chunk| before(chunk.last_key()));
+        if skip > 0 {
+            self.chunk_index += skip;
+            self.iter = self.chunk_iter(self.chunk_index);
+        }
+    }
+}
+
+impl<'a, K: Ord, V> Iterator for PersistentMapIter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.peek_key()?;
+        self.iter.next()
+    }
+}
+
+impl<'a, K: 'a + Ord, V: 'a> SkipAheadMapIterator<'a, K, (&'a K, &'a V)>
+    for PersistentMapIter<'a, K, V>
+{
+    fn advance_past_key<Q>(&mut self, key: &Q) -> &mut Self
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.skip_chunks(|k| k.borrow() <= key);
+        self.iter.advance_past_key(key);
+        self
+    }
+
+    fn advance_until_key<Q>(&mut self, key: &Q) -> &mut Self
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.skip_chunks(|k| k.borrow() < key);
+        self.iter.advance_until_key(key);
+        self
+    }
+
+    fn peek_key(&mut self) -> Option<&'a K> {
+        loop {
+            if let Some(key) = self.iter.peek_key() {
+                return Some(key);
+            } else if self.chunk_index + 1 < self.chunks.len() {
+                self.chunk_index += 1;
+                self.iter = self.chunk_iter(self.chunk_index);
+            } else {
+                return None;
+            }
+        }
+    }
+}
+
+impl<'a, K: Ord + Clone, V: Clone> ToMap<'a, K, V> for PersistentMapIter<'a, K, V> {}
+
+impl<'a, K: Ord, V> MapIterFilter<'a, K, V> for PersistentMapIter<'a, K, V> {}
+
+impl<'a, K: 'a + Ord + Clone, V: 'a> MapIterMerge<'a, K, V> for PersistentMapIter<'a, K, V> {}
+
+impl<'a, K, V, I> BitOr<I> for PersistentMapIter<'a, K, V>
+where
+    K: 'a + Ord + Clone,
+    V: 'a + Clone,
+    I: SkipAheadMapIterator<'a, K, (&'a K, &'a V)>,
+{
+    type Output = MapMergeIter<'a, K, V, Self, I>;
+
+    /// Apply | operator and return a new ordered iterator over the
+    /// contents of this iterator and other.
+    fn bitor(self, other: I) -> Self::Output {
+        self.merge(other)
+    }
+}
+
+// PERSISTENT KEY ITERATOR
+
+/// An Iterator over the keys in a `PersistentOrderedMap`
+pub struct PersistentKeyIter<'a, K: Ord, V> {
+    iter: PersistentMapIter<'a, K, V>,
+}
+
+impl<'a, K: Ord, V> Iterator for PersistentKeyIter<'a, K, V> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(key, _)| key)
+    }
+}
+
+impl<'a, K: 'a + Ord, V: 'a> SkipAheadIterator<K> for PersistentKeyIter<'a, K, V> {
+    fn peek(&mut self) -> Option<&'a K> {
+        self.iter.peek_key()
+    }
+
+    fn advance_past<Q>(&mut self, key: &Q) -> &mut Self
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.iter.advance_past_key(key);
+        self
+    }
+
+    fn advance_until<Q>(&mut self, key: &Q) -> &mut Self
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.iter.advance_until_key(key);
+        self
+    }
+}
+
+impl<'a, K: Ord + Clone, V> ToList<'a, K> for PersistentKeyIter<'a, K, V> {}
+
+impl<'a, K: Ord + Clone, V> ToSet<'a, K> for PersistentKeyIter<'a, K, V> {}
+
+impl<'a, K: Ord + Clone, V> IterSetOperations<K> for PersistentKeyIter<'a, K, V> {}
+
+impl<'a, K: Ord + Clone, V> Selection<'a, K> for PersistentKeyIter<'a, K, V> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ordered_set::ord_set_iterators::SetIter;
+    use crate::test_helpers::random_sequence_below;
+
+    #[test]
+    fn persistent_map_insert_and_remove() {
+        let mut map = PersistentOrderedMap::<u64, u64>::new();
+        let mut ref_map = OrderedMap::<u64, u64>::new();
+        for (i, key) in random_sequence_below(2000, 10_000).into_iter().enumerate() {
+            assert_eq!(map.insert(key, i as u64), ref_map.insert(key, i as u64));
+        }
+        assert!(map.is_valid());
+        assert!(map.chunks.len() > 1);
+        assert_eq!(map.len(), ref_map.len());
+        assert_eq!(map.to_ordered_map(), ref_map);
+        for key in random_sequence_below(1000, 10_000) {
+            assert_eq!(map.remove(&key), ref_map.remove(&key));
+            assert_eq!(map.get(&key), None);
+        }
+        assert!(map.is_valid());
+        assert_eq!(map.to_ordered_map(), ref_map);
+        for key in ref_map.keys() {
+            assert_eq!(map.get(key), ref_map.get(key));
+        }
+    }
+
+    #[test]
+    fn persistent_map_remove_merges_short_chunks() {
+        let mut map: PersistentOrderedMap<u64, u64> = (0..2000).map(|x| (x, x)).collect();
+        let snapshot = map.clone();
+        for key in (0..2000).filter(|x| x % 4 != 0) {
+            assert_eq!(map.remove(&key), Some(key));
+        }
+        assert!(map.is_valid());
+        assert_eq!(map.len(), 500);
+        assert!(map.chunks.len() <= 500 / MIN_CHUNK_LEN + 1);
+        assert!(map.chunks[..map.chunks.len() - 1]
+            .iter()
+            .all(|chunk| chunk.keys.len() >= MIN_CHUNK_LEN));
+        assert!(map.iter().map(|(k, _)| *k).eq((0..500).map(|x| x * 4)));
+        assert_eq!(snapshot.len(), 2000);
+        assert!(snapshot.is_valid());
+        for key in (0..2000).filter(|x| x % 4 == 0) {
+            map.remove(&key);
+        }
+        assert!(map.is_empty());
+        assert!(map.chunks.is_empty());
+    }
+
+    #[test]
+    fn persistent_map_clone_shares_chunks() {
+        let mut map: PersistentOrderedMap<u64, u64> = (0..1000).map(|x| (x, x)).collect();
+        assert!(map.is_valid());
+        let snapshot = map.clone();
+        assert!(Arc::ptr_eq(&map.chunks, &snapshot.chunks));
+        map.insert(500, 0);
+        *map.get_mut(&10).unwrap() = 1;
+        assert!(map.is_valid());
+        assert_eq!(snapshot.get(&500), Some(&500));
+        assert_eq!(snapshot.get(&10), Some(&10));
+        assert_eq!(map.get(&500), Some(&0));
+        assert_eq!(map.get(&10), Some(&1));
+        let shared = map
+            .chunks
+            .iter()
+            .zip(snapshot.chunks.iter())
+            .filter(|(a, b)| Arc::ptr_eq(a, b))
+            .count();
+        assert_eq!(shared, map.chunks.len() - 2);
+        let mut map = snapshot.clone();
+        assert_eq!(map.remove(&999), Some(999));
+        assert!(map.is_valid());
+        assert!(snapshot.contains_key(&999));
+    }
+
+    #[test]
+    fn persistent_map_range() {
+        let map: PersistentOrderedMap<u64, u64> = (0..1000).map(|x| (x * 2, x)).collect();
+        let keys: Vec<u64> = map.range(101..=111).map(|(k, _)| *k).collect();
+        assert_eq!(keys, vec![102, 104, 106, 108, 110]);
+        let keys: Vec<u64> = map.range(120..128).map(|(k, _)| *k).collect();
+        assert_eq!(keys, vec![120, 122, 124, 126]);
+        assert_eq!(map.range(..).count(), 1000);
+        assert_eq!(map.range(1990..).count(), 5);
+        assert_eq!(map.range(200..200).count(), 0);
+        assert_eq!(map.range(3000..).count(), 0);
+        let ref_map = map.to_ordered_map();
+        for (start, end) in &[(0, 1), (127, 129), (250, 1000), (255, 257), (1, 1999)] {
+            assert!(map.range(start..end).eq(ref_map.range(start..end)));
+        }
+    }
+
+    #[test]
+    fn persistent_map_skip_ahead() {
+        let map: PersistentOrderedMap<u64, u64> = (0..1000).map(|x| (x * 2, x)).collect();
+        let mut iter = map.iter();
+        assert_eq!(iter.advance_until_key(&501).next(), Some((&502, &251)));
+        assert_eq!(iter.advance_past_key(&1500).next(), Some((&1502, &751)));
+        assert_eq!(iter.advance_until_key(&1502).next(), Some((&1504, &752)));
+        assert_eq!(iter.advance_past_key(&5000).next(), None);
+        let filtered = map.iter().only(SetIter::new(&[4, 5, 300, 1998])).to_map();
+        assert_eq!(filtered.keys().to_list(), vec![4, 300, 1998]);
+        let mut keys = map.keys();
+        keys.advance_until(&4);
+        let filtered = map.range(..10).except(keys).to_map();
+        assert_eq!(filtered.keys().to_list(), vec![0, 2]);
+        let other: OrderedMap<u64, u64> = (0..10).map(|x| (x * 2 + 1, x)).collect();
+        let merged = (map.range(..20) | other.iter()).to_map();
+        assert!(merged.is_valid());
+        assert_eq!(merged.len(), 20);
+        let keys = map.keys().intersection(other.keys()).to_list();
+        assert!(keys.is_empty());
+    }
+}
@@ -0,0 +1,147 @@
+//! Prefix searches of the sorted collections.
+//!
+//! All of the keys that start with a given prefix are adjacent in a sorted collection
+//! so they can be found with two binary searches. The `Prefix` trait compares a key
+//! with a prefix to give the exact bounds of the run without constructing an upper
+//! bound key (which is easy to get wrong for strings).
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+
+/// Keys that can be compared with a prefix of type `P`.
+///
+/// It is implemented for string keys with `str` prefixes, slice and `Vec` keys with slice
+/// prefixes and for tuple keys with (a borrowed form of) their first field as the prefix.
+pub trait Prefix<P: ?Sized> {
+    /// Return `Ordering::Equal` if this key starts with `prefix` and otherwise whether it
+    /// is before or after all of the keys that do.
+    fn cmp_prefix(&self, prefix: &P) -> Ordering;
+}
+
+/// Tuple keys whose first field can be borrowed as a `Q`. The first field is the prefix
+/// used by `range_first()`.
+pub trait FirstField<Q: ?Sized>: Prefix<Q> {
+    /// Return (a borrowed form of) this key's first field.
+    fn first_field(&self) -> &Q;
+}
+
+// Return the index of the first key starting with `prefix` and the index after the last.
+#[cfg(feature = "alloc")]
+pub(crate) fn prefix_indices<K, P>(keys: &[K], prefix: &P) -> (usize, usize)
+where
+    K: Prefix<P>,
+    P: ?Sized,
+{
+    let start_index = keys.partition_point(|key| key.cmp_prefix(prefix) == Ordering::Less);
+    let end_index = start_index
+        + keys[start_index..].partition_point(|key| key.cmp_prefix(prefix) == Ordering::Equal);
+    (start_index, end_index)
+}
+
+impl Prefix<str> for str {
+    fn cmp_prefix(&self, prefix: &str) -> Ordering {
+        if self.starts_with(prefix) {
+            Ordering::Equal
+        } else {
+            self.cmp(prefix)
+        }
+    }
+}
+
+impl Prefix<str> for &str {
+    fn cmp_prefix(&self, prefix: &str) -> Ordering {
+        (**self).cmp_prefix(prefix)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Prefix<str> for String {
+    fn cmp_prefix(&self, prefix: &str) -> Ordering {
+        self.as_str().cmp_prefix(prefix)
+    }
+}
+
+impl<T: Ord> Prefix<[T]> for [T] {
+    fn cmp_prefix(&self, prefix: &[T]) -> Ordering {
+        if self.starts_with(prefix) {
+            Ordering::Equal
+        } else {
+            self.cmp(prefix)
+        }
+    }
+}
+
+impl<T: Ord> Prefix<[T]> for &[T] {
+    fn cmp_prefix(&self, prefix: &[T]) -> Ordering {
+        (**self).cmp_prefix(prefix)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Ord> Prefix<[T]> for Vec<T> {
+    fn cmp_prefix(&self, prefix: &[T]) -> Ordering {
+        self.as_slice().cmp_prefix(prefix)
+    }
+}
+
+macro_rules! impl_prefix_for_tuple {
+    ( $( ( $( $t:ident ),* ) ),* ) => {
+        $(
+            impl<Q, A, $( $t ),*> Prefix<Q> for (A, $( $t ),*)
+            where
+                Q: Ord + ?Sized,
+                A: Borrow<Q>,
+            {
+                fn cmp_prefix(&self, first: &Q) -> Ordering {
+                    self.first_field().cmp(first)
+                }
+            }
+
+            impl<Q, A, $( $t ),*> FirstField<Q> for (A, $( $t ),*)
+            where
+                Q: Ord + ?Sized,
+                A: Borrow<Q>,
+            {
+                fn first_field(&self) -> &Q {
+                    self.0.borrow()
+                }
+            }
+        )*
+    };
+}
+
+impl_prefix_for_tuple!((B), (B, C), (B, C, D));
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefix_indices_are_exact() {
+        let keys = [
+            "ab",
+            "abc",
+            "abcd",
+            "abc\u{10ffff}",
+            "abd",
+            "abd\u{0}",
+            "b",
+            "\u{10ffff}",
+        ];
+        assert_eq!(prefix_indices(&keys, "abc"), (1, 4));
+        assert_eq!(prefix_indices(&keys, "ab"), (0, 6));
+        assert_eq!(prefix_indices(&keys, ""), (0, 8));
+        assert_eq!(prefix_indices(&keys, "abe"), (6, 6));
+        assert_eq!(prefix_indices(&keys, "\u{10ffff}"), (7, 8));
+        let bytes: [&[u8]; 5] = [&[0, 255], &[1], &[1, 255], &[1, 255, 255], &[2]];
+        assert_eq!(prefix_indices(&bytes, &[1, 255][..]), (2, 4));
+        assert_eq!(prefix_indices(&bytes, &[255][..]), (5, 5));
+        let pairs = [(1, 'a'), (2, 'a'), (2, 'z'), (3, 'a')];
+        assert_eq!(prefix_indices(&pairs, &2), (1, 3));
+        assert_eq!(prefix_indices(&pairs, &0), (0, 0));
+    }
+}
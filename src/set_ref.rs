@@ -0,0 +1,233 @@
+//! Read only views of sets held in borrowed sorted slices.
+//! They give data that is already sorted access to the set algebra without copying.
+
+use core::borrow::Borrow;
+use core::fmt;
+
+use crate::error::OrderError;
+use crate::ordered_set::ord_set_iterators::{
+    a_proper_superset_b, a_superset_b, are_disjoint, Difference, Intersection, SetIter,
+    SymmetricDifference, Union,
+};
+#[cfg(feature = "alloc")]
+use crate::{OrderedSet, Storage, StorageKind};
+
+/// A read only view of a set held in a borrowed slice ordered according to Ord
+/// (with no duplicates).
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OrderedSetRef<'a, T: Ord> {
+    members: &'a [T],
+}
+
+impl<'a, T: Ord> OrderedSetRef<'a, T> {
+    /// Create an `OrderedSetRef` viewing `members` or return an `OrderError` locating
+    /// the first item that is out of order or a duplicate.
+    pub fn new(members: &'a [T]) -> Result<Self, OrderError> {
+        OrderError::check(members)?;
+        Ok(Self { members })
+    }
+
+    /// Create an `OrderedSetRef` viewing `members` which must already be sorted and
+    /// free of duplicates. This is not checked and the set's methods will give
+    /// incorrect results if it is not the case.
+    pub const fn from_sorted_unchecked(members: &'a [T]) -> Self {
+        Self { members }
+    }
+
+    /// Return the number of items in this set.
+    pub const fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    /// Return the set's members as an ordered slice.
+    pub const fn as_slice(&self) -> &'a [T] {
+        self.members
+    }
+
+    /// Return `true` if `item` is a member of the set
+    pub fn contains<Q>(&self, item: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.members
+            .binary_search_by_key(&item, |x| x.borrow())
+            .is_ok()
+    }
+
+    /// Returns a reference to the member of the set that is equal to `item`, if any.
+    pub fn get<Q>(&self, item: &Q) -> Option<&'a T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let index = self
+            .members
+            .binary_search_by_key(&item, |x| x.borrow())
+            .ok()?;
+        Some(&self.members[index])
+    }
+
+    /// Returns the set's first element in ascending order, or `None` if it is empty.
+    pub fn first(&self) -> Option<&'a T> {
+        self.members.first()
+    }
+
+    /// Returns the set's last element in ascending order, or `None` if it is empty.
+    pub fn last(&self) -> Option<&'a T> {
+        self.members.last()
+    }
+
+    /// Returns an iterator that iterates over the set's elements in ascending order
+    pub fn iter(&self) -> SetIter<'a, T> {
+        SetIter::new(self.members)
+    }
+
+    /// Returns an iterator that iterates over the set's elements that fall within the
+    /// given range in ascending order
+    pub fn range<K, R>(&self, range: R) -> SetIter<'a, T>
+    where
+        K: Ord + ?Sized,
+        R: core::ops::RangeBounds<K>,
+        T: Borrow<K>,
+    {
+        let (start_index, end_index) = super::range_indices(self.members, range);
+        SetIter::new(&self.members[start_index..end_index])
+    }
+
+    /// Iterate over the set union of this set and other in ascending order.
+    pub fn union(&self, other: &Self) -> Union<T, SetIter<'a, T>, SetIter<'a, T>> {
+        Union::new(self.iter(), other.iter())
+    }
+
+    /// Iterate over the set intersection of this set and other in ascending order.
+    pub fn intersection(&self, other: &Self) -> Intersection<T, SetIter<'a, T>, SetIter<'a, T>> {
+        Intersection::new(self.iter(), other.iter())
+    }
+
+    /// Iterate over the set difference of this set and other in ascending order.
+    pub fn difference(&self, other: &Self) -> Difference<T, SetIter<'a, T>, SetIter<'a, T>> {
+        Difference::new(self.iter(), other.iter())
+    }
+
+    /// Iterate over the symmetric set difference of this set and other in ascending order.
+    pub fn symmetric_difference(
+        &self,
+        other: &Self,
+    ) -> SymmetricDifference<T, SetIter<'a, T>, SetIter<'a, T>> {
+        SymmetricDifference::new(self.iter(), other.iter())
+    }
+
+    /// Return true if this set is disjoint from the other set
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        are_disjoint(self.iter(), other.iter())
+    }
+
+    /// Return true if self is a subset of other
+    pub fn is_subset(&self, other: &Self) -> bool {
+        a_superset_b(other.iter(), self.iter())
+    }
+
+    /// Return true if self is a proper subset of other
+    pub fn is_proper_subset(&self, other: &Self) -> bool {
+        a_proper_superset_b(other.iter(), self.iter())
+    }
+
+    /// Return true if self is a superset of other
+    pub fn is_superset(&self, other: &Self) -> bool {
+        a_superset_b(self.iter(), other.iter())
+    }
+
+    /// Return true if self is a proper superset of other
+    pub fn is_proper_superset(&self, other: &Self) -> bool {
+        a_proper_superset_b(self.iter(), other.iter())
+    }
+}
+
+impl<T: Ord + fmt::Debug> fmt::Debug for OrderedSetRef<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, T: Ord> IntoIterator for OrderedSetRef<'a, T> {
+    type Item = &'a T;
+    type IntoIter = SetIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: Ord> Clone for OrderedSetRef<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Ord> Copy for OrderedSetRef<'_, T> {}
+
+impl<'a, T: Ord> IntoIterator for &OrderedSetRef<'a, T> {
+    type Item = &'a T;
+    type IntoIter = SetIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T: Ord, S: StorageKind> From<&'a OrderedSet<T, S>> for OrderedSetRef<'a, T> {
+    fn from(set: &'a OrderedSet<T, S>) -> Self {
+        Self::from_sorted_unchecked(set.members.as_slice())
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::ordered_set::ord_set_iterators::{IterSetOperations, ToList, ToSet};
+
+    static SORTED: &[&str] = &["aaa", "bbb", "ccc", "fff", "hhh", "iii", "qqq", "zzz"];
+    static UNSORTED: &[&str] = &["aaa", "bbb", "fff", "ccc", "hhh"];
+
+    #[test]
+    fn set_ref_validation() {
+        assert!(OrderedSetRef::new(SORTED).is_ok());
+        assert_eq!(
+            OrderedSetRef::new(UNSORTED).map_err(|err| err.index()),
+            Err(3)
+        );
+        assert_eq!(
+            OrderedSetRef::new(&[1, 2, 2, 3]).map_err(|err| err.index()),
+            Err(2)
+        );
+        assert!(OrderedSetRef::<u32>::new(&[]).is_ok());
+    }
+
+    #[test]
+    fn set_ref_read_api() {
+        let set = OrderedSetRef::new(SORTED).unwrap();
+        assert_eq!(set.len(), 8);
+        assert!(set.contains("fff"));
+        assert!(!set.contains("ddd"));
+        assert!(core::ptr::eq(set.get("fff").unwrap(), &SORTED[3]));
+        assert_eq!(set.get("ddd"), None);
+        assert_eq!(set.range("c".."i").to_list(), vec!["ccc", "fff", "hhh"]);
+        let other = OrderedSetRef::new(&SORTED[3..6]).unwrap();
+        assert!(other.is_proper_subset(&set));
+        assert_eq!(set.difference(&other).count(), 5);
+        assert_eq!(
+            (set.iter() - other.iter()).to_set(),
+            crate::OrderedSet::from(vec!["aaa", "bbb", "ccc", "qqq", "zzz"])
+        );
+        let owned = set.iter().symmetric_difference(other.iter()).to_set();
+        let view = OrderedSetRef::from(&owned);
+        assert!(view.is_disjoint(&other));
+        assert_eq!(view.union(&other).to_list(), SORTED.to_vec());
+    }
+}
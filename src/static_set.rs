@@ -0,0 +1,157 @@
+//! Sets whose members are sorted at compile time and held in a `'static` slice.
+//! Useful for fixed lookup tables (e.g. reserved keywords) that would otherwise
+//! have to be built lazily at run time.
+
+use crate::set_ref::OrderedSetRef;
+
+/// Creates a `StaticOrderedSet` containing the arguments which are sorted during
+/// compilation. The element type must be given first and may be `&str`, `char` or
+/// any of the primitive integer types. Duplicate arguments are a compile time error.
+///
+/// ```
+/// use ordered_collections::{static_ordered_set, StaticOrderedSet};
+///
+/// static KEYWORDS: StaticOrderedSet<&str> = static_ordered_set!(&str; "let", "fn", "match");
+///
+/// assert!(KEYWORDS.contains("fn"));
+/// assert_eq!(KEYWORDS.first(), Some(&"fn"));
+/// ```
+///
+/// ```compile_fail
+/// use ordered_collections::{static_ordered_set, StaticOrderedSet};
+///
+/// static PRIMES: StaticOrderedSet<u32> = static_ordered_set!(u32; 7, 3, 5, 3);
+/// ```
+#[macro_export]
+macro_rules! static_ordered_set {
+    ( &str; $( $x:expr ),* $(,)? ) => {
+        $crate::static_ordered_set!(@sorted str, &'static str; $( $x ),*)
+    };
+    ( $t:ident; $( $x:expr ),* $(,)? ) => {
+        $crate::static_ordered_set!(@sorted $t, $t; $( $x ),*)
+    };
+    ( @sorted $sort:ident, $t:ty; $( $x:expr ),* ) => {
+        {
+            const MEMBERS: &[$t] = &$crate::static_set::sort::$sort([ $( $x ),* ]);
+            $crate::StaticOrderedSet::from_sorted_unchecked(MEMBERS)
+        }
+    };
+}
+
+/// Compile time sorting of arrays for `static_ordered_set!`. There is one function per
+/// supported element type and each of them panics (failing compilation when evaluated
+/// in a constant) if the array contains duplicates.
+#[doc(hidden)]
+pub mod sort {
+    const DUPLICATE: &str = "duplicate item in static_ordered_set!";
+
+    const fn str_less(a: &str, b: &str) -> Option<bool> {
+        let (a, b) = (a.as_bytes(), b.as_bytes());
+        let mut i = 0;
+        while i < a.len() && i < b.len() {
+            if a[i] != b[i] {
+                return Some(a[i] < b[i]);
+            }
+            i += 1;
+        }
+        if a.len() == b.len() {
+            None
+        } else {
+            Some(a.len() < b.len())
+        }
+    }
+
+    pub const fn str<const N: usize>(mut items: [&'static str; N]) -> [&'static str; N] {
+        let mut i = 1;
+        while i < N {
+            let mut j = i;
+            while j > 0 {
+                match str_less(items[j], items[j - 1]) {
+                    Some(true) => {
+                        let item = items[j];
+                        items[j] = items[j - 1];
+                        items[j - 1] = item;
+                        j -= 1;
+                    }
+                    Some(false) => break,
+                    None => panic!("{}", DUPLICATE),
+                }
+            }
+            i += 1;
+        }
+        items
+    }
+
+    macro_rules! define_sort {
+        ( $( $t:ident ),* ) => {
+            $(
+                pub const fn $t<const N: usize>(mut items: [$t; N]) -> [$t; N] {
+                    let mut i = 1;
+                    while i < N {
+                        let mut j = i;
+                        while j > 0 && items[j] <= items[j - 1] {
+                            if items[j] == items[j - 1] {
+                                panic!("{}", DUPLICATE);
+                            }
+                            let item = items[j];
+                            items[j] = items[j - 1];
+                            items[j - 1] = item;
+                            j -= 1;
+                        }
+                        i += 1;
+                    }
+                    items
+                }
+            )*
+        };
+    }
+
+    define_sort!(char, u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+}
+
+/// A read only set whose members are sorted during compilation and held in a `'static`
+/// slice. Usually created with `static_ordered_set!`.
+pub type StaticOrderedSet<T> = OrderedSetRef<'static, T>;
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::ordered_set::ord_set_iterators::ToList;
+
+    static KEYWORDS: StaticOrderedSet<&str> = static_ordered_set!(
+        &str; "let", "fn", "match", "if", "else", "loop", "while", "for", "in", "impl", "i",
+    );
+    static PRIMES: StaticOrderedSet<u32> = static_ordered_set!(u32; 13, 2, 7, 3, 11, 5);
+    static ODDS: StaticOrderedSet<u32> = static_ordered_set!(u32; 9, 7, 5, 3, 1);
+
+    #[test]
+    fn static_set_is_sorted() {
+        assert_eq!(
+            KEYWORDS.iter().to_list(),
+            vec!["else", "fn", "for", "i", "if", "impl", "in", "let", "loop", "match", "while"]
+        );
+        assert!(KEYWORDS.contains("impl"));
+        assert!(!KEYWORDS.contains("struct"));
+        assert_eq!(
+            KEYWORDS.range("i".."l").to_list(),
+            vec!["i", "if", "impl", "in"]
+        );
+        assert_eq!(PRIMES.as_slice(), &[2, 3, 5, 7, 11, 13]);
+        let empty: StaticOrderedSet<char> = static_ordered_set!(char;);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn static_set_operations() {
+        assert_eq!((PRIMES.iter() & ODDS.iter()).to_list(), vec![3, 5, 7]);
+        assert_eq!((PRIMES.iter() - ODDS.iter()).to_list(), vec![2, 11, 13]);
+        assert_eq!(
+            (PRIMES.iter() ^ ODDS.iter()).to_list(),
+            vec![1, 2, 9, 11, 13]
+        );
+        assert_eq!(PRIMES.union(&ODDS).count(), 8);
+        assert!(!PRIMES.is_disjoint(&ODDS));
+        assert!(ODDS.is_superset(&static_ordered_set!(u32; 3, 5, 7)));
+        assert!(PRIMES.intersection(&ODDS).eq(ODDS.range(3..=7)));
+    }
+}
@@ -0,0 +1,273 @@
+//! Backing stores for the sorted lists that underlie the ordered collections.
+
+use core::fmt;
+#[cfg(feature = "alloc")]
+use core::iter::FromIterator;
+use core::mem::{self, MaybeUninit};
+use core::ptr;
+use core::slice;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "alloc")]
+mod inline;
+
+#[cfg(feature = "alloc")]
+pub use self::inline::{Inline, InlineIntoIter, InlineStore};
+
+/// A sequence of items that provides slice access plus insertion and removal at an index.
+/// The ordered collections keep the store's contents sorted.
+#[cfg(feature = "alloc")]
+pub trait Storage<T>: Default + FromIterator<T> + IntoIterator<Item = T> {
+    /// Return the store's contents as a slice.
+    fn as_slice(&self) -> &[T];
+
+    /// Return the store's contents as a mutable slice.
+    fn as_mut_slice(&mut self) -> &mut [T];
+
+    /// Insert `item` at position `index` moving all items after it up one place.
+    fn insert(&mut self, index: usize, item: T);
+
+    /// Remove and return the item at position `index` moving all items after it down one place.
+    fn remove(&mut self, index: usize) -> T;
+
+    /// Remove all items from the store.
+    fn clear(&mut self);
+
+    /// Return the number of items that the store can hold without allocating.
+    fn capacity(&self) -> usize;
+}
+
+/// Selects the `Storage` that backs an `OrderedSet` whatever its item type, so that the
+/// item type is not repeated in the set's type (e.g. `OrderedSet<T, Inline<8>>`).
+#[cfg(feature = "alloc")]
+pub trait StorageKind {
+    /// The store that holds items of type `T`.
+    type Store<T>: Storage<T>;
+}
+
+/// The default `StorageKind` which holds the items in a `Vec`.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Heap;
+
+#[cfg(feature = "alloc")]
+impl StorageKind for Heap {
+    type Store<T> = Vec<T>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Storage<T> for Vec<T> {
+    fn as_slice(&self) -> &[T] {
+        self
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        self
+    }
+
+    fn insert(&mut self, index: usize, item: T) {
+        Vec::insert(self, index, item)
+    }
+
+    fn remove(&mut self, index: usize) -> T {
+        Vec::remove(self, index)
+    }
+
+    fn clear(&mut self) {
+        Vec::clear(self)
+    }
+
+    fn capacity(&self) -> usize {
+        Vec::capacity(self)
+    }
+}
+
+// ARRAY BUFFER
+
+/// A fixed capacity buffer of up to `N` items stored inline.
+pub(crate) struct ArrayBuffer<T, const N: usize> {
+    len: usize,
+    items: [MaybeUninit<T>; N],
+}
+
+impl<T, const N: usize> ArrayBuffer<T, N> {
+    pub(crate) fn new() -> Self {
+        Self {
+            len: 0,
+            // An array of `MaybeUninit` requires no initialisation.
+            items: unsafe { MaybeUninit::<[MaybeUninit<T>; N]>::uninit().assume_init() },
+        }
+    }
+
+    pub(crate) fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    pub(crate) fn as_slice(&self) -> &[T] {
+        // The first `len` items are initialised.
+        unsafe { slice::from_raw_parts(self.items.as_ptr() as *const T, self.len) }
+    }
+
+    pub(crate) fn as_mut_slice(&mut self) -> &mut [T] {
+        // The first `len` items are initialised.
+        unsafe { slice::from_raw_parts_mut(self.items.as_mut_ptr() as *mut T, self.len) }
+    }
+
+    /// Insert `item` at position `index` or return it if the buffer is full.
+    pub(crate) fn try_insert(&mut self, index: usize, item: T) -> Result<(), T> {
+        assert!(index <= self.len, "insertion index out of bounds");
+        if self.is_full() {
+            return Err(item);
+        }
+        unsafe {
+            let p = (self.items.as_mut_ptr() as *mut T).add(index);
+            ptr::copy(p, p.add(1), self.len - index);
+            ptr::write(p, item);
+        }
+        self.len += 1;
+        Ok(())
+    }
+
+    pub(crate) fn remove(&mut self, index: usize) -> T {
+        assert!(index < self.len, "removal index out of bounds");
+        self.len -= 1;
+        unsafe {
+            let p = (self.items.as_mut_ptr() as *mut T).add(index);
+            let item = ptr::read(p);
+            ptr::copy(p.add(1), p, self.len - index);
+            item
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        let len = self.len;
+        // Set the length first so that a panicking `drop()` cannot cause a double drop.
+        self.len = 0;
+        unsafe {
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(
+                self.items.as_mut_ptr() as *mut T,
+                len,
+            ));
+        }
+    }
+
+    // Move the contents into a `Vec` with room for at least `additional` more items.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn drain_to_vec(&mut self, additional: usize) -> Vec<T> {
+        let mut vec = Vec::with_capacity(self.len + additional);
+        let len = self.len;
+        self.len = 0;
+        unsafe {
+            ptr::copy_nonoverlapping(self.items.as_ptr() as *const T, vec.as_mut_ptr(), len);
+            vec.set_len(len);
+        }
+        vec
+    }
+}
+
+impl<T, const N: usize> Drop for ArrayBuffer<T, N> {
+    fn drop(&mut self) {
+        self.clear()
+    }
+}
+
+impl<T: Clone, const N: usize> Clone for ArrayBuffer<T, N> {
+    fn clone(&self) -> Self {
+        let mut buffer = Self::new();
+        for item in self.as_slice() {
+            // cannot fail as the buffer has the same capacity as `self`
+            let _ = buffer.try_insert(buffer.len, item.clone());
+        }
+        buffer
+    }
+}
+
+impl<T, const N: usize> IntoIterator for ArrayBuffer<T, N> {
+    type Item = T;
+    type IntoIter = ArrayIntoIter<T, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let buffer = mem::ManuallyDrop::new(self);
+        ArrayIntoIter {
+            // ownership of the initialised items passes to the iterator
+            items: unsafe { ptr::read(&buffer.items) },
+            index: 0,
+            len: buffer.len,
+        }
+    }
+}
+
+/// An iterator that moves the items out of an inline buffer.
+pub struct ArrayIntoIter<T, const N: usize> {
+    items: [MaybeUninit<T>; N],
+    index: usize,
+    len: usize,
+}
+
+impl<T, const N: usize> Iterator for ArrayIntoIter<T, N> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index < self.len {
+            self.index += 1;
+            // Each initialised item is read exactly once.
+            Some(unsafe { ptr::read(self.items[self.index - 1].as_ptr()) })
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len - self.index, Some(self.len - self.index))
+    }
+}
+
+impl<T, const N: usize> Drop for ArrayIntoIter<T, N> {
+    fn drop(&mut self) {
+        for item in &mut self.items[self.index..self.len] {
+            unsafe { ptr::drop_in_place(item.as_mut_ptr()) };
+        }
+    }
+}
+
+// CAPACITY ERROR
+
+/// The error returned when inserting into a fixed capacity collection that is full.
+/// It hands back the rejected item.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CapacityError<T = ()> {
+    element: T,
+}
+
+impl<T> CapacityError<T> {
+    pub(crate) fn new(element: T) -> Self {
+        Self { element }
+    }
+
+    /// Return the item that could not be inserted.
+    pub fn element(self) -> T {
+        self.element
+    }
+
+    /// Discard the rejected item.
+    pub fn simplify(self) -> CapacityError {
+        CapacityError { element: () }
+    }
+}
+
+impl<T> fmt::Debug for CapacityError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CapacityError: insufficient capacity")
+    }
+}
+
+impl<T> fmt::Display for CapacityError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "insufficient capacity")
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> std::error::Error for CapacityError<T> {}
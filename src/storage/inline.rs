@@ -0,0 +1,224 @@
+use core::cmp::Ordering;
+use core::hash::{Hash, Hasher};
+use core::iter::FromIterator;
+
+use alloc::vec::Vec;
+
+use super::{ArrayBuffer, ArrayIntoIter, Storage, StorageKind};
+
+// INLINE STORAGE
+
+enum InlineRepr<T, const N: usize> {
+    Array(ArrayBuffer<T, N>),
+    Heap(Vec<T>),
+}
+
+/// The `StorageKind` that holds up to `N` items inline (without allocating) in an
+/// `InlineStore` that spills onto the heap if more items are inserted, e.g.
+/// `OrderedSet<T, Inline<8>>`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Inline<const N: usize>;
+
+impl<const N: usize> StorageKind for Inline<N> {
+    type Store<T> = InlineStore<T, N>;
+}
+
+/// A `Storage` that holds up to `N` items inline (without allocating) and spills
+/// onto the heap if more items are inserted.
+pub struct InlineStore<T, const N: usize> {
+    repr: InlineRepr<T, N>,
+}
+
+impl<T, const N: usize> InlineStore<T, N> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return `true` if the items have been moved onto the heap.
+    pub fn spilled(&self) -> bool {
+        matches!(self.repr, InlineRepr::Heap(_))
+    }
+}
+
+impl<T, const N: usize> Default for InlineStore<T, N> {
+    fn default() -> Self {
+        Self {
+            repr: InlineRepr::Array(ArrayBuffer::new()),
+        }
+    }
+}
+
+impl<T, const N: usize> Storage<T> for InlineStore<T, N> {
+    fn as_slice(&self) -> &[T] {
+        match &self.repr {
+            InlineRepr::Array(buffer) => buffer.as_slice(),
+            InlineRepr::Heap(vec) => vec,
+        }
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        match &mut self.repr {
+            InlineRepr::Array(buffer) => buffer.as_mut_slice(),
+            InlineRepr::Heap(vec) => vec,
+        }
+    }
+
+    fn insert(&mut self, index: usize, item: T) {
+        match &mut self.repr {
+            InlineRepr::Array(buffer) => {
+                if let Err(item) = buffer.try_insert(index, item) {
+                    let mut vec = buffer.drain_to_vec(N);
+                    vec.insert(index, item);
+                    self.repr = InlineRepr::Heap(vec);
+                }
+            }
+            InlineRepr::Heap(vec) => vec.insert(index, item),
+        }
+    }
+
+    fn remove(&mut self, index: usize) -> T {
+        match &mut self.repr {
+            InlineRepr::Array(buffer) => buffer.remove(index),
+            InlineRepr::Heap(vec) => vec.remove(index),
+        }
+    }
+
+    fn clear(&mut self) {
+        match &mut self.repr {
+            InlineRepr::Array(buffer) => buffer.clear(),
+            InlineRepr::Heap(vec) => vec.clear(),
+        }
+    }
+
+    fn capacity(&self) -> usize {
+        match &self.repr {
+            InlineRepr::Array(_) => N,
+            InlineRepr::Heap(vec) => vec.capacity(),
+        }
+    }
+}
+
+impl<T, const N: usize> FromIterator<T> for InlineStore<T, N> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut inline = Self::default();
+        for item in iter {
+            let len = inline.as_slice().len();
+            inline.insert(len, item);
+        }
+        inline
+    }
+}
+
+impl<T, const N: usize> IntoIterator for InlineStore<T, N> {
+    type Item = T;
+    type IntoIter = InlineIntoIter<T, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        match self.repr {
+            InlineRepr::Array(buffer) => InlineIntoIter::Array(buffer.into_iter()),
+            InlineRepr::Heap(vec) => InlineIntoIter::Heap(vec.into_iter()),
+        }
+    }
+}
+
+/// An iterator that moves the items out of an `InlineStore`.
+pub enum InlineIntoIter<T, const N: usize> {
+    Array(ArrayIntoIter<T, N>),
+    Heap(alloc::vec::IntoIter<T>),
+}
+
+impl<T, const N: usize> Iterator for InlineIntoIter<T, N> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            InlineIntoIter::Array(iter) => iter.next(),
+            InlineIntoIter::Heap(iter) => iter.next(),
+        }
+    }
+}
+
+impl<T: Clone, const N: usize> Clone for InlineStore<T, N> {
+    fn clone(&self) -> Self {
+        let repr = match &self.repr {
+            InlineRepr::Array(buffer) => InlineRepr::Array(buffer.clone()),
+            InlineRepr::Heap(vec) => InlineRepr::Heap(vec.clone()),
+        };
+        Self { repr }
+    }
+}
+
+impl<T: core::fmt::Debug, const N: usize> core::fmt::Debug for InlineStore<T, N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        self.as_slice().fmt(f)
+    }
+}
+
+impl<T: PartialEq, const N: usize> PartialEq for InlineStore<T, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T: Eq, const N: usize> Eq for InlineStore<T, N> {}
+
+impl<T: PartialOrd, const N: usize> PartialOrd for InlineStore<T, N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.as_slice().partial_cmp(other.as_slice())
+    }
+}
+
+impl<T: Ord, const N: usize> Ord for InlineStore<T, N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_slice().cmp(other.as_slice())
+    }
+}
+
+impl<T: Hash, const N: usize> Hash for InlineStore<T, N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    #[test]
+    fn inline_storage_spills() {
+        let mut store = InlineStore::<u32, 4>::new();
+        for (i, item) in [5, 1, 3, 7].iter().enumerate() {
+            store.insert(i, *item);
+        }
+        assert!(!store.spilled());
+        assert_eq!(store.capacity(), 4);
+        store.insert(2, 9);
+        assert!(store.spilled());
+        assert_eq!(store.as_slice(), &[5, 1, 9, 3, 7]);
+        assert_eq!(store.remove(0), 5);
+        assert_eq!(store.as_slice(), &[1, 9, 3, 7]);
+        let items: Vec<u32> = store.into_iter().collect();
+        assert_eq!(items, vec![1, 9, 3, 7]);
+    }
+
+    #[test]
+    fn inline_storage_drops_items() {
+        let item = Rc::new(0);
+        let mut store: InlineStore<Rc<u32>, 4> = (0..3).map(|_| Rc::clone(&item)).collect();
+        assert_eq!(Rc::strong_count(&item), 4);
+        let clone = store.clone();
+        assert_eq!(Rc::strong_count(&item), 7);
+        drop(store.remove(1));
+        assert_eq!(Rc::strong_count(&item), 6);
+        drop(clone);
+        assert_eq!(Rc::strong_count(&item), 3);
+        let mut iter = store.into_iter();
+        iter.next();
+        drop(iter);
+        assert_eq!(Rc::strong_count(&item), 1);
+        let mut store: InlineStore<Rc<u32>, 4> = (0..3).map(|_| Rc::clone(&item)).collect();
+        store.clear();
+        assert_eq!(Rc::strong_count(&item), 1);
+    }
+}
@@ -0,0 +1,60 @@
+//! Helpers shared by the crate's unit tests.
+
+#[cfg(feature = "alloc")]
+use std::vec::Vec;
+
+/// The names from the standard library prelude that the tests use. They are not in
+/// scope when the crate is built without the `std` feature. Not every configuration's
+/// tests need all of them.
+#[allow(unused_imports)]
+pub(crate) mod prelude {
+    pub(crate) use std::string::{String, ToString};
+    pub(crate) use std::vec::Vec;
+}
+
+/// A simple (and deterministic) pseudo random number generator.
+#[cfg(feature = "alloc")]
+pub(crate) struct LinearCongrentialGenerator {
+    a: u64,
+    m: u64,
+    x: u64,
+}
+
+#[cfg(feature = "alloc")]
+impl LinearCongrentialGenerator {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self {
+            a: (1 << 18) + 1,
+            m: (1 << 35),
+            x: seed,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Iterator for LinearCongrentialGenerator {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.x = (self.x * self.a + 1) % self.m;
+        Some(self.x)
+    }
+}
+
+/// Return `length` pseudo random numbers.
+#[cfg(feature = "alloc")]
+pub(crate) fn random_sequence(length: usize) -> Vec<u64> {
+    LinearCongrentialGenerator::new(length as u64)
+        .take(length)
+        .collect()
+}
+
+/// Return `length` pseudo random numbers less than `bound` (so that there are likely
+/// to be some duplicates).
+#[cfg(feature = "alloc")]
+pub(crate) fn random_sequence_below(length: usize, bound: u64) -> Vec<u64> {
+    LinearCongrentialGenerator::new(length as u64)
+        .take(length)
+        .map(|x| x % bound)
+        .collect()
+}